@@ -0,0 +1,75 @@
+use std::{hint::black_box, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fto_solver::{pruning::{heuristic::PruningHeuristic, statesetfto2::StateSetFTO2, statesetpyra::StateSetPyra}, progress::NoProgress, solver::idastar::Heuristic, state::{statefto::StateFTO, statefto2::StateFTO2, statepyra::StatePyra, symhash::SymHash}, tablefile::{get_table_dir, MappedTable}, tables::{SymTables, TableGroup, Tables}};
+use rand::{rngs::StdRng, SeedableRng};
+
+
+// Lookup before the Index: Position of the Representant in the Table
+fn linear_sym_hash<S: SymHash>(state: &S, table: &[u32]) -> usize {
+    let repr = state.get_repr();
+    table.iter().position(|&other| repr == other as usize).unwrap()
+}
+
+fn load_tables() -> Arc<Tables> {
+    let tables = Tables::load_or_generate(get_table_dir(), Arc::new(NoProgress));
+    tables.require(&[TableGroup::Pyra, TableGroup::FTO2]).unwrap();
+    Arc::new(tables)
+}
+
+fn sym_lookup(c: &mut Criterion) {
+    let pyra_tables = load_tables().pyra().unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    let pyras: Vec<StatePyra> = (0..1000).map(|_| StatePyra::scrambled_with(&mut rng)).collect();
+
+    let mut group = c.benchmark_group("pyra sym lookup");
+    group.bench_function("linear scan", |b| b.iter(|| {
+        pyras.iter().map(|pyra| linear_sym_hash(pyra, pyra_tables.symhash())).sum::<usize>()
+    }));
+    group.bench_function("binary search", |b| b.iter(|| {
+        pyras.iter().map(|pyra| pyra.get_sym_hash(pyra_tables.symhash()).unwrap()).sum::<usize>()
+    }));
+    group.bench_function("index", |b| b.iter(|| {
+        pyras.iter().map(|pyra| pyra.get_sym_hash_indexed(pyra_tables.symindex())).sum::<usize>()
+    }));
+    group.finish();
+}
+
+// Optimal Distances of a Batch of States, as the Solvers compute them
+fn batch_distances(c: &mut Criterion) {
+    let tables = load_tables();
+    let mut rng = StdRng::seed_from_u64(0);
+    let pyras: Vec<StateFTO> = (0..100).map(|_| StateFTO::from_pyra(&StatePyra::scrambled_with(&mut rng))).collect();
+    let fto2s: Vec<StateFTO2> = (0..100).map(|_| StateFTO2::scrambled_with(&mut rng)).collect();
+
+    let pyra_heuristics: [(&str, PruningHeuristic<StateFTO, StateSetPyra, MappedTable>); 3] = [
+        ("linear scan", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto: &StateFTO| linear_sym_hash(&StatePyra::from_fto(fto).unwrap(), tables.symhash())).unwrap()),
+        ("binary search", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto: &StateFTO| StatePyra::from_fto(fto).unwrap().get_sym_hash(tables.symhash()).unwrap()).unwrap()),
+        ("index", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto: &StateFTO| StatePyra::from_fto(fto).unwrap().get_sym_hash_indexed(tables.symindex())).unwrap())
+    ];
+    let mut group = c.benchmark_group("pyra batch distances");
+    group.sample_size(10);
+    for (name, heuristic) in &pyra_heuristics {
+        group.bench_function(*name, |b| b.iter(|| {
+            pyras.iter().map(|fto| heuristic.get_bound(black_box(fto)) as usize).sum::<usize>()
+        }));
+    }
+    group.finish();
+
+    let fto2_heuristics: [(&str, PruningHeuristic<StateFTO2, StateSetFTO2, MappedTable>); 3] = [
+        ("linear scan", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto2: &StateFTO2| linear_sym_hash(fto2, tables.symhash())).unwrap()),
+        ("binary search", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto2: &StateFTO2| fto2.get_sym_hash(tables.symhash()).unwrap()).unwrap()),
+        ("index", PruningHeuristic::map(tables.clone(), 8, |tables: &Arc<SymTables>, fto2: &StateFTO2| fto2.get_sym_hash_indexed(tables.symindex())).unwrap())
+    ];
+    let mut group = c.benchmark_group("fto2 batch distances");
+    group.sample_size(10);
+    for (name, heuristic) in &fto2_heuristics {
+        group.bench_function(*name, |b| b.iter(|| {
+            fto2s.iter().map(|fto2| heuristic.get_bound(black_box(fto2)) as usize).sum::<usize>()
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sym_lookup, batch_distances);
+criterion_main!(benches);
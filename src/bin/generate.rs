@@ -1,38 +1,62 @@
+use std::{path::PathBuf, process::exit, sync::Arc};
+
 use clap::Parser;
-use fto_solver::{moving::{sym::SymTable, turn::TurnTable}, pruning::{statesetfto2::StateSetFTO2, statesetfto2split::StateSetFTO2Split, statesetpyra::StateSetPyra, PruningTable}, state::{statecenters::StateCenters, statecorners::StateCorners, statefto2::StateFTO2, statepyra::StatePyra, symhash::SymHash}};
+use fto_solver::{error::Error, progress::{default_progress, PrintProgress}, pruning::{statesetfto2::StateSetFTO2, statesetfto2split::StateSetFTO2Split, statesetftocenters::StateSetFTOCenters, statesetftocorners::StateSetFTOCorners, statesetftocornerscenters::StateSetFTOCornersCenters, statesetftoedges::StateSetFTOEdges, statesetftoedgescorners::StateSetFTOEdgesCorners, statesetftosubgroup::StateSetFTOSubgroup, statesetpyra::StateSetPyra, PruningTable}, tablefile::get_table_dir, tables::{TableGroup, Tables}};
 
 
 /// Generate Table for the FTO Solver
 #[derive(Parser)]
 struct Cli {
-    /// The table to generate: aux, fto2, fto2split
+    /// The table to generate: aux, aux2, fto, pyra, fto2, fto2split, ftocenters, ftoedges, ftocorners, ftocornerscenters, ftoedgescorners, ftosubgroup
     table: String,
-    /// Disables Progress Output
+    /// Disables the Progress Bars
     #[clap(long, short, action)]
     quiet: bool,
-    /// Fills Max Depth at end
+    /// Resumes from the checkpoint of a stopped run
     #[clap(long, short, action)]
-    filllast: bool
+    resume: bool,
+    /// Directory of the tables, defaults to $FTO_SOLVER_TABLES or ./tables
+    #[clap(long, short)]
+    tables: Option<PathBuf>
 }
 
 fn main() {
     let args = Cli::parse();
+    let dir = args.tables.unwrap_or_else(get_table_dir);
+    let progress = if args.quiet {Arc::new(PrintProgress)} else {default_progress()};
+    let load_tables = || Tables::load_or_generate(&dir, progress.clone());
     match args.table.as_str() {
-        "aux" => {
-            <StatePyra as SymHash>::generate_table();
-            <StatePyra as TurnTable>::generate_table();
+        "aux" | "aux2" => {Tables::generate(dir, progress).require(&TableGroup::ALL).unwrap_or_else(|err| fail(err));}
+        "fto" => {Tables::generate(dir, progress).require(&TableGroup::FTO).unwrap_or_else(|err| fail(err));}
+        "pyra" => {PruningTable::<StateSetPyra>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "fto2" => {PruningTable::<StateSetFTO2>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "fto2split" => {PruningTable::<StateSetFTO2Split>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "ftocenters" => {PruningTable::<StateSetFTOCenters>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "ftoedges" => {PruningTable::<StateSetFTOEdges>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "ftosubgroup" => {PruningTable::<StateSetFTOSubgroup>::new(&load_tables()).generate(args.resume).unwrap_or_else(|err| fail(err));}
+        "ftocorners" => {
+            let tables = load_tables();
+            PruningTable::<StateSetFTOCorners<0>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOCorners<1>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOCorners<2>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+        }
+        "ftocornerscenters" => {
+            let tables = load_tables();
+            PruningTable::<StateSetFTOCornersCenters<1>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOCornersCenters<2>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
         }
-        "aux2" => {
-            <StateCorners as SymHash>::generate_table();
-            <StateCorners as TurnTable>::generate_table();
-            <StateCenters as TurnTable>::generate_table();
-            <StateCenters as SymTable>::generate_table();
-            <StateFTO2 as SymHash>::generate_table();
-            <StateFTO2 as TurnTable>::generate_table();
+        "ftoedgescorners" => {
+            let tables = load_tables();
+            PruningTable::<StateSetFTOEdgesCorners<0>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOEdgesCorners<1>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOEdgesCorners<2>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
+            PruningTable::<StateSetFTOEdgesCorners<3>>::new(&tables).generate(args.resume).unwrap_or_else(|err| fail(err));
         }
-        "pyra" => {PruningTable::<StateSetPyra>::new(!args.quiet).generate(args.filllast);}
-        "fto2" => {PruningTable::<StateSetFTO2>::new(!args.quiet).generate(args.filllast);}
-        "fto2split" => {PruningTable::<StateSetFTO2Split>::new(!args.quiet).generate(args.filllast);}
         _ => {}
     }
-}
\ No newline at end of file
+}
+
+fn fail(err: Error) -> ! {
+    eprintln!("error: {err}");
+    exit(1)
+}
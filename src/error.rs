@@ -0,0 +1,81 @@
+use std::{fmt, io};
+
+use crate::{puzzle::sequence::{ParseMoveError, ParseSequenceError}, state::{statefto::FaceletError, StateError}};
+
+
+// Errors of the Library, so that bad Input and broken Tables can be handled by the Caller
+#[derive(Debug)]
+pub enum Error {
+    ParseMove(ParseMoveError),
+    ParseSequence(ParseSequenceError),
+    Facelets(FaceletError),
+    InvalidState(StateError),
+    Io(io::Error),
+    // Name of the Subgroup the State isn't in
+    NotInSubgroup(&'static str),
+    // Hash and Size of the States
+    HashOutOfRange(usize, usize),
+    // Representant missing in the SymHash Table
+    MissingRepresentant(usize),
+    // The Search ended without reaching the solved State
+    NoSolution
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseMove(err) => write!(f, "{err}"),
+            Self::ParseSequence(err) => write!(f, "{err}"),
+            Self::Facelets(err) => write!(f, "{err}"),
+            Self::InvalidState(err) => write!(f, "State can't be solved: {err}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::NotInSubgroup(subgroup) => write!(f, "State is not in the {subgroup} subgroup"),
+            Self::HashOutOfRange(hash, size) => write!(f, "Hash {hash} out of range, expected less than {size}"),
+            Self::MissingRepresentant(repr) => write!(f, "Representant {repr} missing in the symhash table"),
+            Self::NoSolution => write!(f, "No solution found")
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseMove(err) => Some(err),
+            Self::ParseSequence(err) => Some(err),
+            Self::Facelets(err) => Some(err),
+            Self::InvalidState(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<ParseMoveError> for Error {
+    fn from(err: ParseMoveError) -> Self {
+        Self::ParseMove(err)
+    }
+}
+
+impl From<ParseSequenceError> for Error {
+    fn from(err: ParseSequenceError) -> Self {
+        Self::ParseSequence(err)
+    }
+}
+
+impl From<FaceletError> for Error {
+    fn from(err: FaceletError) -> Self {
+        Self::Facelets(err)
+    }
+}
+
+impl From<StateError> for Error {
+    fn from(err: StateError) -> Self {
+        Self::InvalidState(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
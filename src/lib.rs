@@ -2,4 +2,8 @@ pub mod puzzle;
 pub mod state;
 pub mod moving;
 pub mod pruning;
-pub mod solver;
\ No newline at end of file
+pub mod solver;
+pub mod tablefile;
+pub mod tables;
+pub mod error;
+pub mod progress;
\ No newline at end of file
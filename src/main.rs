@@ -1,34 +1,168 @@
-use fto_solver::{solver::solverpyra::SolverPyra, state::{statefto::StateFTO, statepyra::StatePyra}};
+use std::{path::{Path, PathBuf}, process::exit, sync::Arc};
+
+use clap::{Parser, Subcommand};
+use rand::{rngs::StdRng, SeedableRng};
+use fto_solver::{error::Error, pruning::{stateset::StateSet, statesetfto2::StateSetFTO2, statesetpyra::StateSetPyra}, puzzle::{sequence::{Move, Sequence}, Puzzle}, solver::{solverfto::SolverFTO, solverfto2::SolverFTO2, solverfto2split::SolverFTO2Split, solverpyra::SolverPyra}, state::{statefto::StateFTO, statefto2::StateFTO2, statepyra::StatePyra}, progress::default_progress, tablefile::get_table_dir, tables::Tables};
+
+
+/// Solver for the FTO and its Subpuzzles
+#[derive(Parser)]
+#[command(name = "fto-solver")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Directory of the tables, defaults to $FTO_SOLVER_TABLES or ./tables
+    #[clap(long, short, global = true)]
+    tables: Option<PathBuf>
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solves a Scramble or a State
+    Solve {
+        /// The puzzle to solve: pyra, fto2, fto2split, fto
+        #[clap(long, short)]
+        puzzle: String,
+        /// Prints the State before and after the Solution
+        #[clap(long, short, action)]
+        show: bool,
+        /// The Scramble as Moves, for the fto also the State as 72 Facelets
+        #[clap(required = true, allow_hyphen_values = true)]
+        input: Vec<String>
+    },
+    /// Generates Scrambles of random States, one per line
+    Scramble {
+        /// The puzzle to scramble: pyra, fto2, fto2split, fto
+        #[clap(long, short)]
+        puzzle: String,
+        /// Number of Scrambles
+        #[clap(long, short, default_value_t = 1)]
+        count: usize,
+        /// Seed for reproducible Scrambles
+        #[clap(long, short = 'S')]
+        seed: Option<u64>,
+        /// Draws new States until the Scramble has at least this many Moves,
+        /// at most the longest optimal Solution, the fto gives up after 100 States
+        #[clap(long, short, default_value_t = 0)]
+        min_length: usize
+    }
+}
 
 fn main() {
-    // let mut fto = StateFTO2::scrambled();
-    // println!("{fto}");
-    // // fto.inverse();
-    // let solver = SolverFTO2::new();
-    // let scramble = solver.solve(&fto);
-    // println!("{scramble}");
-    // fto.do_sequence(&scramble);
-    // println!("{fto}");
-
-    // println!("{solution} ({})", StateFTO2::get_sequence_len(solution.as_str()));
-    // fto.do_sequence(&solution);
-    // println!("{fto}");
-
-    let mut pyra = StatePyra::scrambled();
-    let fto = StateFTO::from_pyra(&pyra);
-    println!("{fto}");
-    pyra.inverse();
-    let solver = SolverPyra::new();
-    let scramble = solver.solve(&pyra);
-    println!("{scramble}");
-
-    // // let poss: u128 = 43_252_003_274_489_856_000;
-    // let poss: u128 = 31_408_133_379_194_880_000_000;
-    // let comb: u128 = _calc_comb_fto(21, 0);
-    // println!("{}", comb);
-    // if comb > poss {
-    //     println!("Exhausted Permutations");
-    // }
+    let args = Cli::parse();
+    let dir = args.tables.unwrap_or_else(get_table_dir);
+    match args.command {
+        Command::Solve { puzzle, show, input } => {
+            let input = input.join(" ");
+            match puzzle.as_str() {
+                "pyra" => {
+                    let solver = SolverPyra::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_solution(parse_scramble::<StatePyra>(&input), |pyra| solver.solve(pyra), show);
+                }
+                "fto2" => {
+                    let solver = SolverFTO2::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_solution(parse_scramble::<StateFTO2>(&input), |fto2| solver.solve(fto2), show);
+                }
+                "fto2split" => {
+                    let solver = SolverFTO2Split::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_solution(parse_scramble::<StateFTO2>(&input), |fto2| solver.solve(fto2), show);
+                }
+                "fto" => {
+                    let solver = SolverFTO::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_solution(parse_fto(&input), |fto| solver.solve(fto), show);
+                }
+                _ => fail(format!("Unknown puzzle \"{puzzle}\", expected pyra, fto2, fto2split or fto"))
+            }
+        }
+        Command::Scramble { puzzle, count, seed, min_length } => {
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_os_rng()
+            };
+            match puzzle.as_str() {
+                "pyra" => {
+                    let solver = SolverPyra::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_scrambles(|| StatePyra::scrambled_with(&mut rng), |pyra| solver.solve(pyra), count, min_length, Some(StateSetPyra::MAX_DEPTH));
+                }
+                "fto2" => {
+                    let solver = SolverFTO2::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_scrambles(|| StateFTO2::scrambled_with(&mut rng), |fto2| solver.solve(fto2), count, min_length, Some(StateSetFTO2::MAX_DEPTH));
+                }
+                "fto2split" => {
+                    let solver = SolverFTO2Split::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_scrambles(|| StateFTO2::scrambled_with(&mut rng), |fto2| solver.solve(fto2), count, min_length, Some(StateSetFTO2::MAX_DEPTH));
+                }
+                "fto" => {
+                    let solver = SolverFTO::new(load_tables(&dir)).unwrap_or_else(|err| fail(err.to_string()));
+                    print_scrambles(|| StateFTO::scrambled_with(&mut rng), |fto| solver.solve(fto), count, min_length, None);
+                }
+                _ => fail(format!("Unknown puzzle \"{puzzle}\", expected pyra, fto2, fto2split or fto"))
+            }
+        }
+    }
+}
+
+// Tables are only loaded once the Puzzle is known
+fn load_tables(dir: &Path) -> Arc<Tables> {
+    Arc::new(Tables::load_or_generate(dir, default_progress()))
+}
+
+// Applies the Scramble to the solved State
+fn parse_scramble<P: Puzzle>(input: &str) -> P {
+    let mut state = P::solved();
+    state.do_notation(input).unwrap_or_else(|err| fail(err.to_string()));
+    state
+}
+
+// Reads the FTO from Facelets, if the Input is no Scramble
+fn parse_fto(input: &str) -> StateFTO {
+    if input.parse::<Sequence<StateFTO>>().is_ok() || !looks_like_facelets(input) {
+        return parse_scramble(input);
+    }
+    StateFTO::from_facelets(input).unwrap_or_else(|err| fail(err.to_string()))
+}
+
+// 72 Stickers, or only Color Letters without Moves, so that Facelets of the wrong Length get a Facelet Error
+fn looks_like_facelets(input: &str) -> bool {
+    input.split_whitespace().map(str::len).sum::<usize>() == 72 || input.split_whitespace().all(|token| {
+        token.parse::<Move<StateFTO>>().is_err() && token.chars().all(StateFTO::is_color_letter)
+    })
+}
+
+fn print_solution<P: Puzzle>(mut state: P, solve: impl Fn(&P) -> Result<Sequence<P>, Error>, show: bool) {
+    if show {
+        println!("{state}");
+    }
+    let solution = solve(&state).unwrap_or_else(|err| fail(err.to_string()));
+    println!("{solution} ({})", solution.len());
+    if show {
+        state.do_sequence(&solution);
+        println!("{state}");
+    }
+}
+
+// The Scramble of a random State is the inverted Solution
+// Optimal Solutions are at most max_length long, so a longer min_length fails at once,
+// without a max_length the Tries per Scramble are limited
+fn print_scrambles<P: Puzzle>(mut random: impl FnMut() -> P, solve: impl Fn(&P) -> Result<Sequence<P>, Error>, count: usize, min_length: usize, max_length: Option<u8>) {
+    if let Some(max_length) = max_length.filter(|&max_length| min_length > max_length as usize) {
+        fail(format!("Minimum length {min_length} exceeds the maximum solution length {max_length}"));
+    }
+    let max_tries = if max_length.is_some() {usize::MAX} else {MAX_TRIES};
+    for _ in 0..count {
+        let scramble = (0..max_tries).map(|_| solve(&random()).unwrap_or_else(|err| fail(err.to_string())).inverse())
+            .find(|scramble| scramble.len() >= min_length)
+            .unwrap_or_else(|| fail(format!("No scramble with at least {min_length} moves in {MAX_TRIES} tries")));
+        println!("{scramble}");
+    }
+}
+
+// Random States drawn for one Scramble of a Puzzle without max_length, each Solution takes Seconds
+const MAX_TRIES: usize = 100;
+
+fn fail(message: String) -> ! {
+    eprintln!("error: {message}");
+    exit(1)
 }
 
 fn _calc_comb_std(depth: u8, split: u8) -> u128 {
@@ -55,4 +189,27 @@ fn _calc_comb_fto(depth: u8, split: u8) -> u128 {
             _ => 1
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use fto_solver::state::State;
+
+    use super::*;
+
+    #[test]
+    fn facelets_of_any_length_are_recognized() {
+        let facelets = StateFTO::solved().to_facelets();
+        assert!(looks_like_facelets(&facelets));
+        assert!(looks_like_facelets(&facelets[1..]));
+        assert!(looks_like_facelets(&facelets[..40].to_lowercase()));
+        assert!(looks_like_facelets("YYYYYYYYY BBBB"));
+    }
+
+    #[test]
+    fn scrambles_are_no_facelets() {
+        assert!(!looks_like_facelets("R U' BL"));
+        assert!(!looks_like_facelets("R U X"));
+        assert!(!looks_like_facelets("R BB"));
+    }
+}
@@ -1,75 +1,59 @@
-use std::{fs, time::Instant};
+use std::{path::Path, time::Instant};
 
-use bytemuck::cast_slice;
-use indicatif::{ProgressBar, ProgressStyle};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use crate::{pruning::format_duration, state::State};
+use crate::{error::Error, progress::ProgressSink, state::RawHash, tablefile::{get_table_file, read_hash_table, write_hash_table, TableKind}};
 
 // also: convert hash (or sym hash) to raw
 
-pub trait TurnTable: State + Clone {
+pub trait TurnTable: RawHash + Clone {
     const TURNTABLE_NAME: &str;
     const NUM_HASHES: usize;
-    fn from_turntable_index(hash: usize) -> Self;
-    fn get_turntable_entry(&self) -> usize;
+    // Turn Tables indexed by Sym Hashes look the States up in their SymTables
+    type Index: Sync;
+    fn from_turntable_index(index: &Self::Index, hash: usize) -> Self;
+    fn get_turntable_entry(&self, index: &Self::Index) -> Result<usize, Error>;
     // input: hash (or sym hash), move
     // output: hash (or sym hash)
 
-    fn get_turn_table() -> Vec<usize> {
-        match Self::load_table() {
-            Ok(table) => table,
-            Err(_) => {
-                Self::generate_table()
-            }
-        }
+    fn apply_turn_table(table: &Vec<u32>, hash: usize, turn: u8) -> usize {
+        table[hash * Self::NUM_TURNS as usize + turn as usize] as usize
     }
 
-    fn apply_turn_table(table: &Vec<usize>, hash: usize, turn: u8) -> usize {
-        table[hash * Self::NUM_TURNS as usize + turn as usize]
+    fn apply_turns_table(table: &Vec<u32>, hash: usize) -> Vec<usize> {
+        table[hash * Self::NUM_TURNS as usize .. (hash + 1) * Self::NUM_TURNS as usize].iter().map(|&entry| entry as usize).collect()
     }
 
-    fn apply_turns_table(table: &Vec<usize>, hash: usize) -> Vec<usize> {
-        table[hash * Self::NUM_TURNS as usize .. (hash + 1) * Self::NUM_TURNS as usize].to_vec()
-    }
-
-    fn generate_table() -> Vec<usize> {
-        println!("Generating table {}", Self::get_file_name());
+    fn generate_table(index: &Self::Index, dir: &Path, progress: &dyn ProgressSink) -> Result<Vec<u32>, Error> {
+        progress.start(&Self::get_file_name(dir), Self::NUM_HASHES as u64);
         let now = Instant::now();
-        let bar = ProgressBar::new(Self::NUM_HASHES as u64);
-        bar.set_style(ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {pos:>10}/{len:10}").unwrap().progress_chars("#>-"));
-        let table: Vec<usize> = (0..Self::NUM_HASHES).into_par_iter().flat_map_iter(|hash| {
-            bar.inc(1);
-            let state = Self::from_turntable_index(hash);
+        let table = (0..Self::NUM_HASHES).into_par_iter().flat_map_iter(|hash| {
+            progress.entries_filled(1);
+            let state = Self::from_turntable_index(index, hash);
             (0..Self::NUM_TURNS).map(move |turn| {
                 let mut adj = state.clone();
                 adj.apply_turn(turn);
-                adj.get_turntable_entry()
+                adj.get_turntable_entry(index).map(|entry| entry as u32)
             })
-        }).collect();
+        }).collect::<Result<Vec<u32>, Error>>()?;
         let elapsed = now.elapsed();
-        bar.finish();
-        println!("Finished generating Table in {}", format_duration(elapsed));
-        println!("Size: {}", table.len());
-        Self::save_table(&table);
-        table
+        progress.done(elapsed);
+        progress.message(&format!("Size: {}", table.len()));
+        Self::save_table(dir, &table)?;
+        progress.message(&format!("Saved table {}", Self::get_file_name(dir)));
+        Ok(table)
     }
 
-    fn save_table(table: &Vec<usize>) {
-        match fs::write(Self::get_file_name(), cast_slice(table)) {
-            Ok(_) => println!("Saved table {}", Self::get_file_name()),
-            Err(_) => println!("Couldn't write table {}", Self::get_file_name())
-        }
+    fn save_table(dir: &Path, table: &Vec<u32>) -> Result<(), Error> {
+        write_hash_table(&Self::get_file_name(dir), TableKind::Turn, Self::TURNTABLE_NAME, table)?;
+        Ok(())
     }
 
-    fn load_table() -> Result<Vec<usize>, std::io::Error> {
-        match fs::read(Self::get_file_name()) {
-            Ok(data) => Ok(cast_slice(&data).to_vec()),
-            Err(err) => Err(err)
-        }
+    fn load_table(dir: &Path) -> Result<Vec<u32>, std::io::Error> {
+        read_hash_table(&Self::get_file_name(dir), TableKind::Turn, Self::TURNTABLE_NAME, Self::NUM_HASHES * Self::NUM_TURNS as usize)
     }
 
-    fn get_file_name() -> String {
-        format!("tables/turn/{}.bin", Self::TURNTABLE_NAME)
+    fn get_file_name(dir: &Path) -> String {
+        get_table_file(dir, "turn", &format!("{}.bin", Self::TURNTABLE_NAME))
     }
 }
\ No newline at end of file
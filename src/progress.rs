@@ -0,0 +1,155 @@
+use std::{sync::Arc, time::Duration};
+
+#[cfg(feature = "indicatif")]
+use std::sync::Mutex;
+
+#[cfg(feature = "indicatif")]
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::pruning::format_duration;
+
+
+// Receives the Progress of generating Tables, the Events of one Table come from multiple Threads
+pub trait ProgressSink: Send + Sync {
+    // Generating the Table starts, size is its Number of Entries
+    fn start(&self, _table: &str, _size: u64) {}
+
+    // A Depth of a Pruning Table starts, with the Number of States to open
+    fn depth_start(&self, _depth: u8, _states: u64) {}
+
+    // States of the current Depth were opened
+    fn states_opened(&self, _count: u64) {}
+
+    fn entries_filled(&self, _count: u64) {}
+
+    // Status and Warnings, which don't stop the Generation
+    fn message(&self, _message: &str) {}
+
+    fn done(&self, _elapsed: Duration) {}
+}
+
+// Progress Bars, if the indicatif Feature is enabled, else printed Messages
+pub fn default_progress() -> Arc<dyn ProgressSink> {
+    #[cfg(feature = "indicatif")]
+    return Arc::new(IndicatifProgress::new());
+    #[cfg(not(feature = "indicatif"))]
+    return Arc::new(PrintProgress);
+}
+
+// Ignores all Progress
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {}
+
+// Prints the Messages without Progress Bars
+pub struct PrintProgress;
+
+impl ProgressSink for PrintProgress {
+    fn start(&self, table: &str, _size: u64) {
+        println!("Generating table {table}");
+    }
+
+    fn message(&self, message: &str) {
+        println!("{message}");
+    }
+
+    fn done(&self, elapsed: Duration) {
+        println!("Finished generating Table in {}", format_duration(elapsed));
+    }
+}
+
+// Draws a Bar for the filled Entries and one for the opened States of the current Depth
+// The Bars live as long as the Sink, so that the Threads count on them without locking,
+// only the MultiProgress of the current Table is locked
+#[cfg(feature = "indicatif")]
+pub struct IndicatifProgress {
+    pb_table: ProgressBar,
+    pb_closed: ProgressBar,
+    // MultiProgress of the current Table and whether it shows the Bar of the Depth
+    multipb: Mutex<Option<(MultiProgress, bool)>>
+}
+
+#[cfg(feature = "indicatif")]
+impl IndicatifProgress {
+    pub fn new() -> Self {
+        let pb_table = ProgressBar::hidden();
+        pb_table.set_style(ProgressStyle::with_template(
+            "[{elapsed_precise}] Table: {bar:40.cyan/blue} {percent_precise:>7}% {msg}"
+        )
+        .unwrap()
+        .progress_chars("#>-"));
+        let pb_closed = ProgressBar::hidden();
+        pb_closed.set_style(ProgressStyle::with_template(
+            "Pruning Depth {msg:>2}: {bar:40.gray/white} {percent_precise:>7}%"
+        )
+        .unwrap()
+        .progress_chars("##-"));
+        Self {
+            pb_table,
+            pb_closed,
+            multipb: Mutex::new(None)
+        }
+    }
+}
+
+#[cfg(feature = "indicatif")]
+impl Default for IndicatifProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "indicatif")]
+impl ProgressSink for IndicatifProgress {
+    fn start(&self, table: &str, size: u64) {
+        println!("Generating table {table}");
+        let multipb = MultiProgress::new();
+        self.pb_table.reset();
+        self.pb_table.set_length(size);
+        multipb.add(self.pb_table.clone());
+        *self.multipb.lock().unwrap() = Some((multipb, false));
+    }
+
+    fn depth_start(&self, depth: u8, states: u64) {
+        if let Some((multipb, shows_closed)) = self.multipb.lock().unwrap().as_mut() {
+            if !*shows_closed {
+                self.pb_closed.reset();
+            }
+            self.pb_closed.set_position(0);
+            self.pb_closed.set_length(states);
+            self.pb_closed.set_message(format!("{}", depth));
+            if !*shows_closed {
+                multipb.add(self.pb_closed.clone());
+                *shows_closed = true;
+            }
+        }
+    }
+
+    fn states_opened(&self, count: u64) {
+        self.pb_closed.inc(count);
+    }
+
+    fn entries_filled(&self, count: u64) {
+        self.pb_table.inc(count);
+    }
+
+    // Prints above the Bars, so that they aren't torn apart
+    fn message(&self, message: &str) {
+        match self.multipb.lock().unwrap().as_ref() {
+            Some((multipb, _)) => {
+                let _ = multipb.println(message);
+            }
+            None => println!("{message}")
+        }
+    }
+
+    fn done(&self, elapsed: Duration) {
+        if let Some((_, shows_closed)) = self.multipb.lock().unwrap().take() {
+            self.pb_table.finish();
+            if shows_closed {
+                self.pb_closed.finish();
+            }
+        }
+        println!("Finished generating Table in {}", format_duration(elapsed));
+    }
+}
@@ -0,0 +1,116 @@
+use std::{io::ErrorKind, ops::Deref, sync::Arc};
+
+use crate::{error::Error, solver::idastar::Heuristic, state::State, tablefile::MappedTable, tables::Tables};
+
+use super::{stateset::StateSet, PruningTable};
+
+
+// Decodes the Depths mod 3 of a Pruning Table into Lower Bounds
+// Unfilled Entries are at least filled_depth deep
+// The Table is either read into memory or mapped from its file
+pub struct PruningHeuristic<S: State, T: StateSet + Sync + Send, D: Deref<Target = [u8]> = Vec<u8>> {
+    lookup: T::Lookup,
+    table: D,
+    filled_depth: u8,
+    num_turns: u8,
+    hash: fn(&T::Lookup, &S) -> usize,
+    _marker: std::marker::PhantomData<T>
+}
+
+impl<S: State, T: StateSet + Sync + Send, D: Deref<Target = [u8]>> PruningHeuristic<S, T, D> {
+    // All States closer than filled_depth need to be filled in the Table
+    pub fn new(lookup: T::Lookup, table: D, filled_depth: u8, num_turns: u8, hash: fn(&T::Lookup, &S) -> usize) -> Self {
+        Self {
+            lookup,
+            table,
+            filled_depth,
+            num_turns,
+            hash,
+            _marker: std::marker::PhantomData
+        }
+    }
+
+    fn get_depthm3(&self, state: &S) -> u8 {
+        PruningTable::<T>::read(&self.table, (self.hash)(&self.lookup, state))
+    }
+}
+
+impl<S: State, T: StateSet + Sync + Send> PruningHeuristic<S, T> {
+    // Reads the complete Table from file or generates it
+    pub fn load(tables: Arc<Tables>, num_turns: u8, hash: fn(&T::Lookup, &S) -> usize) -> Result<Self, Error> {
+        let lookup = T::lookup(&tables)?;
+        let table = match PruningTable::<T>::read_table_from_file(tables.dir()) {
+            Ok(table) => table,
+            Err(err) => {
+                if err.kind() == ErrorKind::InvalidData {
+                    tables.progress().message(&err.to_string());
+                }
+                PruningTable::<T>::new(&tables).generate(false)?
+            }
+        };
+        Ok(Self::new(lookup, table, T::MAX_DEPTH + 1, num_turns, hash))
+    }
+}
+
+impl<S: State, T: StateSet + Sync + Send> PruningHeuristic<S, T, MappedTable> {
+    // Maps the complete Table from file, generates the file first if needed
+    pub fn map(tables: Arc<Tables>, num_turns: u8, hash: fn(&T::Lookup, &S) -> usize) -> Result<Self, Error> {
+        let lookup = T::lookup(&tables)?;
+        let table = match PruningTable::<T>::map_table_from_file(tables.dir()) {
+            Ok(table) => table,
+            Err(err) => {
+                if err.kind() == ErrorKind::InvalidData {
+                    tables.progress().message(&err.to_string());
+                }
+                PruningTable::<T>::new(&tables).generate(false)?;
+                PruningTable::<T>::map_table_from_file(tables.dir())?
+            }
+        };
+        Ok(Self::new(lookup, table, T::MAX_DEPTH + 1, num_turns, hash))
+    }
+}
+
+impl<S: State, T: StateSet + Sync + Send, D: Deref<Target = [u8]>> Heuristic<S> for PruningHeuristic<S, T, D> {
+    // Gets the exact Depth by following the Table down to 0
+    fn get_bound(&self, state: &S) -> u8 {
+        let mut depthm3 = self.get_depthm3(state);
+        if depthm3 == 3 {
+            return self.filled_depth;
+        }
+        let mut current = state.clone();
+        let mut depth = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for turn in 0..self.num_turns {
+                let mut next = current.clone();
+                next.apply_turn(turn);
+                let next_depthm3 = self.get_depthm3(&next);
+                if next_depthm3 != 3 && (next_depthm3 + 1) % 3 == depthm3 {
+                    current = next;
+                    depthm3 = next_depthm3;
+                    depth += 1;
+                    changed = true;
+                    break;
+                }
+            }
+        }
+        depth
+    }
+
+    // The Depth of the next State differs by at most one
+    fn get_next_bound(&self, next: &S, bound: u8) -> u8 {
+        let depthm3 = self.get_depthm3(next);
+        if depthm3 == 3 {
+            self.filled_depth.max(bound.saturating_sub(1))
+        } else if bound >= self.filled_depth {
+            self.filled_depth - 1
+        } else if depthm3 == (bound + 1) % 3 {
+            bound + 1
+        } else if depthm3 == bound % 3 {
+            bound
+        } else {
+            bound - 1
+        }
+    }
+}
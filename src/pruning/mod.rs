@@ -1,203 +1,277 @@
 
-use std::{fs, str::FromStr, sync::{Arc, RwLock}, time::{Duration, Instant}};
+use std::{fs, io::ErrorKind, path::Path, sync::atomic::{AtomicU64, AtomicU8, Ordering}, time::{Duration, Instant}};
 
-use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
-use rayon::iter::{IntoParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+pub mod heuristic;
 pub mod stateset;
 pub mod statesetfto2;
 pub mod statesetfto2split;
+pub mod statesetftocenters;
+pub mod statesetftocornerscenters;
+pub mod statesetftoedges;
+pub mod statesetftocorners;
+pub mod statesetftoedgescorners;
+pub mod statesetftosubgroup;
 
 pub mod statesetpyra;
 
 use stateset::StateSet;
 
-pub struct PruningTable <S: StateSet + Sync + Send> {
-    pb_table: ProgressBar,
-    pb_closed: ProgressBar,
+use crate::{error::Error, tables::Tables, tablefile::{get_table_file, map_table, read_table, read_table_any_count, write_table, MappedTable, TableHeader, TableKind}};
+
+// Table, closed Table, Pruning Depth and whether the Search goes backward
+type Checkpoint = (Vec<AtomicU8>, Vec<usize>, u8, bool);
+
+// The Progress goes to the Sink of the Tables
+pub struct PruningTable <'a, S: StateSet + Sync + Send> {
+    tables: &'a Tables,
+    filled: AtomicU64,
     _marker: std::marker::PhantomData<S>
 }
 
-impl<S: StateSet + Sync + Send> PruningTable<S> {
-    pub fn new(display_progress: bool) -> Self {
-        let _multipb = if display_progress {MultiProgress::new()} else {MultiProgress::with_draw_target(ProgressDrawTarget::hidden())};
-        let pb_table = _multipb.add(ProgressBar::new(S::SIZE as u64));
-        let pb_closed = _multipb.add(ProgressBar::new(1));
-
-        let style_table: ProgressStyle = ProgressStyle::with_template(
-            "[{elapsed_precise}] Table: {bar:40.cyan/blue} {percent_precise:>7}% {msg}"
-        )
-        .unwrap()
-        .progress_chars("#>-");
-        let style_opened = ProgressStyle::with_template(
-            "Pruning Depth {msg:>2}: {bar:40.gray/white} {percent_precise:>7}%"
-        )
-        .unwrap()
-        .progress_chars("##-");
-
-        pb_table.set_style(style_table);
-        pb_closed.set_style(style_opened);
-        pb_closed.set_message(format!("{}", 0));
-
+impl<'a, S: StateSet + Sync + Send> PruningTable<'a, S> {
+    pub fn new(tables: &'a Tables) -> Self {
         Self {
-            pb_table,
-            pb_closed,
+            tables,
+            filled: AtomicU64::new(0),
             _marker: std::marker::PhantomData
         }
     }
 
-    pub fn generate(&self, fill_last: bool) -> Vec<u8> {
+    // Continues from the last Checkpoint, if resume is set and a Checkpoint was saved
+    pub fn generate(&self, resume: bool) -> Result<Vec<u8>, Error> {
+        let lookup = S::lookup(self.tables)?;
         // Starts time measurement
+        let progress = self.tables.progress();
+        progress.start(&Self::get_file_name(self.tables.dir()), S::SIZE as u64);
         let now = Instant::now();
 
-        // Creates empty Pruning Table
-        let shared_table: Arc<RwLock<Vec<u8>>> = Arc::new(RwLock::new(vec![255 as u8; (S::SIZE + 3) / 4]));
-
-        // Creates empty closed Table
-        let mut closed = vec![];
-
-        // Fills in the solved State and adds first closed State
-        let state = S::solved();
-        closed.push(state.get_hash());
-        if Self::write_shared(&shared_table, state.get_hash(), 0).is_ok() {
-            self.pb_table.inc(1);
-        }
-        for hash in state.get_sym_hashes() {
-            if Self::write_shared(&shared_table, hash, 0).is_ok() {
-                self.pb_table.inc(1);
+        let checkpoint = if resume {
+            match self.load_checkpoint() {
+                Ok(checkpoint) => {
+                    progress.message(&format!("Resuming at Pruning Depth {}", checkpoint.2));
+                    Some(checkpoint)
+                }
+                Err(err) => {
+                    progress.message(&format!("Couldn't resume: {err}"));
+                    None
+                }
             }
+        } else {
+            None
+        };
+        let shared_table = self.search(&lookup, checkpoint.unwrap_or_else(|| self.start(&lookup)), u8::MAX, true);
+
+        // Finishes Time measurement
+        let elapsed = now.elapsed();
+        // Completes Progress
+        progress.done(elapsed);
+        if !self.table_is_full() {
+            progress.message(&format!("Table not full: {}/{}", self.filled.load(Ordering::Relaxed), S::SIZE));
         }
 
-        // Starts looping over the Pruning Depths
-        let mut pruning_depth = 1;
-        while !closed.is_empty() && !self.table_is_full() {
-            // Shows Progress
-            self.clear_pb_closed(closed.len() as u64, pruning_depth);
+        // Saves Table to file
+        progress.message("Saving Table to file");
+        let table: Vec<u8> = shared_table.into_iter().map(AtomicU8::into_inner).collect();
+        write_table(&Self::get_file_name(self.tables.dir()), &Self::get_header(), &table)?;
+        let _ = fs::remove_file(Self::get_checkpoint_name(self.tables.dir()));
+        Ok(table)
+    }
+
+    // Fills only the States up to the Depth and keeps the Table in memory,
+    // for a PruningHeuristic with a filled_depth of depth + 1
+    pub fn generate_to_depth(&self, depth: u8) -> Result<Vec<u8>, Error> {
+        let lookup = S::lookup(self.tables)?;
+        let shared_table = self.search(&lookup, self.start(&lookup), depth, false);
+        Ok(shared_table.into_iter().map(AtomicU8::into_inner).collect())
+    }
+
+    // Searches the Pruning Depths up to max_depth, saves a Checkpoint after each one, if checkpoints is set
+    fn search(&self, lookup: &S::Lookup, checkpoint: Checkpoint, max_depth: u8, checkpoints: bool) -> Vec<AtomicU8> {
+        let progress = self.tables.progress();
+        let (shared_table, mut closed, mut pruning_depth, mut backward) = checkpoint;
 
-            // Iterates over all States in closed Table
+        // Starts looping over the Pruning Depths
+        while (backward || !closed.is_empty()) && !self.table_is_full() && pruning_depth <= max_depth {
             let depthm3 = pruning_depth % 3;
-            let at_max = pruning_depth == S::MAX_DEPTH - 1 && fill_last;
-            closed = closed.into_par_iter().flat_map_iter(|curr_state_set| {
+            // Switches to searching backward, once fewer Entries are empty than States are to be opened
+            if !backward && S::SIZE as u64 - self.filled.load(Ordering::Relaxed) < closed.len() as u64 {
+                backward = true;
+                closed = vec![];
+                progress.message("Searching backward");
+            }
+
+            if backward {
                 // Shows Progress
-                self.pb_closed.inc(1);
-
-                // Opens the next States
-                S::from_hash(curr_state_set).get_next_state_sets().into_iter().filter_map(|next_state_set| {
-                    let hash = next_state_set.get_hash();
-                    // Tries to write State to Table
-                    match Self::write_shared(&shared_table, hash, depthm3) {
-                        Ok(_) => {
-                            // On write, also write symmetric States
-                            let inc = 1 + next_state_set.get_sym_hashes().into_iter().filter(|&sym_hash| {
-                                Self::write_shared(&shared_table, sym_hash, depthm3) == Ok(())
-                            }).count();
-                            // Increase Progressbar
-                            self.pb_table.inc(inc as u64);
-                            if !at_max {
+                progress.depth_start(pruning_depth, S::SIZE as u64);
+
+                // Iterates over all empty Entries
+                let prev_depthm3 = (pruning_depth - 1) % 3;
+                let found = (0..S::SIZE).into_par_iter().filter(|&hash| {
+                    // Shows Progress
+                    progress.states_opened(1);
+
+                    // Writes the State, if one of the next States was found at the previous Depth
+                    // States written at this Depth can't be mistaken for the previous one
+                    Self::read_shared(&shared_table, hash) == 3
+                    && S::from_hash(hash).get_next_state_sets(lookup).into_iter().any(|next_state_set| {
+                        Self::read_shared(&shared_table, next_state_set.get_hash()) == prev_depthm3
+                    })
+                    && Self::write_shared(&shared_table, hash, depthm3).is_ok()
+                }).count();
+                self.fill(found as u64);
+                // Stops, if the remaining Entries can't be reached
+                if found == 0 {
+                    break;
+                }
+            } else {
+                // Shows Progress
+                progress.depth_start(pruning_depth, closed.len() as u64);
+
+                // Iterates over all States in closed Table
+                closed = closed.into_par_iter().flat_map_iter(|curr_state_set| {
+                    // Shows Progress
+                    progress.states_opened(1);
+
+                    // Opens the next States
+                    S::from_hash(curr_state_set).get_next_state_sets(lookup).into_iter().filter_map(|next_state_set| {
+                        let hash = next_state_set.get_hash();
+                        // Tries to write State to Table
+                        match Self::write_shared(&shared_table, hash, depthm3) {
+                            Ok(_) => {
+                                // On write, also write symmetric States
+                                let inc = 1 + next_state_set.get_sym_hashes(lookup).into_iter().filter(|&sym_hash| {
+                                    Self::write_shared(&shared_table, sym_hash, depthm3) == Ok(())
+                                }).count();
+                                // Increase Progressbar
+                                self.fill(inc as u64);
                                 Some(hash)
-                            } else {
-                                None
                             }
+                            Err(_) => None
                         }
-                        Err(_) => None
-                    }
-                })
-            }).collect();
+                    })
+                }).collect();
+            }
             // Increases the Pruning Depth
             pruning_depth += 1;
-        }
 
-        // Fills rest of the Table
-        if pruning_depth == S::MAX_DEPTH && !self.table_is_full() && fill_last {
-            let depthm3 = pruning_depth % 3;
-            // Shows Progress
-            self.pb_table.set_message("Filling rest");
-            self.clear_pb_closed(S::SIZE as u64 - self.pb_table.position(), pruning_depth);
-
-            // Iterates Table
-            let mut table = shared_table.write().unwrap();
-            table.par_iter_mut().for_each(|table_value| {
-                let mut changed: u64 = 0;
-                // Fills empty entries
-                for entry_index in 0..4 {
-                    let true_entry_index = entry_index << 1;
-                    if (*table_value >> true_entry_index) & 3 == 3 {
-                        *table_value = (depthm3 << true_entry_index) + (*table_value & !(3 << true_entry_index));
-                        changed += 1;
-                    }
-                };
-                // Shows progress
-                self.pb_table.inc(changed);
-                self.pb_closed.inc(changed);
-            });
+            // Saves the Progress, so that a stopped Generation can be resumed
+            if checkpoints {
+                if let Err(err) = self.save_checkpoint(&shared_table, &closed, pruning_depth, backward) {
+                    progress.message(&format!("Couldn't save checkpoint: {err}"));
+                }
+            }
         }
+        shared_table
+    }
 
-        // Finishes Time measurement
-        let elapsed = now.elapsed();
-        // Completes Progress
-        let filled = self.table_is_full();
-        let fill_level = self.pb_table.position();
-        self.pb_table.finish();
-        self.pb_closed.finish();
-        println!("Finished generating Table in {}", format_duration(elapsed));
-        if !filled {
-            println!("Table not full: {}/{}", fill_level, self.pb_table.length().unwrap());
+    // Creates empty Pruning Table, shared between the Threads without locking,
+    // and fills in the solved States as first closed States
+    fn start(&self, lookup: &S::Lookup) -> Checkpoint {
+        let shared_table: Vec<AtomicU8> = (0..S::SIZE.div_ceil(4)).map(|_| AtomicU8::new(255)).collect();
+        let mut closed = vec![];
+        for state in S::get_solved_state_sets(lookup) {
+            if Self::write_shared(&shared_table, state.get_hash(), 0).is_ok() {
+                closed.push(state.get_hash());
+                self.fill(1);
+            }
+            for hash in state.get_sym_hashes(lookup) {
+                if Self::write_shared(&shared_table, hash, 0).is_ok() {
+                    self.fill(1);
+                }
+            }
         }
+        (shared_table, closed, 1, false)
+    }
 
-        // Saves Table to file
-        println!("Saving Table to file");
-        let table = shared_table.read().unwrap();
-        fs::write(Self::get_file_name(), table.clone()).expect("Saving Table failed!");
-        table.clone()
+    // Checkpoint Data: Pruning Depth, Direction, Table and closed Table as u64 Entries
+    fn save_checkpoint(&self, shared_table: &[AtomicU8], closed: &[usize], pruning_depth: u8, backward: bool) -> Result<(), std::io::Error> {
+        let mut data = Vec::with_capacity(2 + shared_table.len() + closed.len() * 8);
+        data.push(pruning_depth);
+        data.push(backward as u8);
+        data.extend(shared_table.iter().map(|table_value| table_value.load(Ordering::Relaxed)));
+        data.extend(closed.iter().flat_map(|&hash| (hash as u64).to_le_bytes()));
+        let header = TableHeader {
+            kind: TableKind::Checkpoint,
+            name: S::NAME,
+            entry_bits: 8,
+            count: data.len() as u64
+        };
+        write_table(&Self::get_checkpoint_name(self.tables.dir()), &header, &data)
+    }
+
+    fn load_checkpoint(&self) -> Result<Checkpoint, std::io::Error> {
+        let data = read_table_any_count(&Self::get_checkpoint_name(self.tables.dir()), TableKind::Checkpoint, S::NAME, 8)?;
+        let table_len = S::SIZE.div_ceil(4);
+        if data.len() < 2 + table_len || (data.len() - 2 - table_len) % 8 != 0 {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, "Checkpoint doesn't match the table size"));
+        }
+        let shared_table: Vec<AtomicU8> = data[2..2 + table_len].iter().map(|&table_value| AtomicU8::new(table_value)).collect();
+        let closed = data[2 + table_len..].chunks_exact(8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()) as usize).collect();
+        // Shows Progress of the filled Entries
+        let filled = (0..S::SIZE).filter(|&index| Self::read_shared(&shared_table, index) != 3).count();
+        self.fill(filled as u64);
+        Ok((shared_table, closed, data[0], data[1] == 1))
     }
 
     fn table_is_full(&self) -> bool {
-        self.pb_table.position() >= S::SIZE as u64
+        self.filled.load(Ordering::Relaxed) >= S::SIZE as u64
     }
 
-    // Resets the Progressbar for the closed Table
-    fn clear_pb_closed(&self, closed_len: u64, slice_depth: u8) {
-        self.pb_closed.set_position(0);
-        self.pb_closed.set_length(closed_len);
-        self.pb_closed.set_message(format!("{}", slice_depth));
+    // Counts the filled Entries
+    fn fill(&self, count: u64) {
+        self.filled.fetch_add(count, Ordering::Relaxed);
+        self.tables.progress().entries_filled(count);
     }
 
-    // Writes a value into the index of the shared Table
-    fn write_shared(shared_table: &Arc<RwLock<Vec<u8>>>, index: usize, value: u8) -> Result<(), ()> {
+    // Writes a value into the index of the shared Table, fails if the entry is already filled
+    // Compare and swap retries, when another Thread changed an entry of the same Byte
+    fn write_shared(shared_table: &[AtomicU8], index: usize, value: u8) -> Result<(), ()> {
         let entry_index = (index & 3) << 1;
-        if {
-            // Gets Lock
-            let table = shared_table.read().unwrap();
-            // Checks, if entry is already filled
-            let table_value = table[index >> 2];
-            (table_value >> entry_index) & 3 == 3
-        } {
-            // Gets Lock
-            let mut table = shared_table.write().unwrap();
-            // Gets entry
-            let table_value = table[index >> 2];
-            // Writes new u2 entry onto u8 slot
+        shared_table[index >> 2].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |table_value| {
+            // Checks, if entry is still empty
             if (table_value >> entry_index) & 3 == 3 {
-                table[index >> 2] = (value << entry_index) + (table_value & !(3 << entry_index));
-                Ok(())
+                // Writes new u2 entry onto u8 slot
+                Some((value << entry_index) + (table_value & !(3 << entry_index)))
             } else {
-                Err(())
+                None
             }
-        } else {
-            Err (())
-        }
+        }).map(|_| ()).map_err(|_| ())
     }
 
-    pub fn read(table: &Vec<u8>, index: usize) -> u8 {
+    fn read_shared(shared_table: &[AtomicU8], index: usize) -> u8 {
+        (shared_table[index >> 2].load(Ordering::Relaxed) >> ((index & 3) << 1)) & 3
+    }
+
+    pub fn read(table: &[u8], index: usize) -> u8 {
         (table[index >> 2] >> ((index & 3) << 1)) & 3
     }
 
-    pub fn read_table_from_file() -> Result<Vec<u8>, std::io::Error> {
-        fs::read(Self::get_file_name())
+    pub fn read_table_from_file(dir: &Path) -> Result<Vec<u8>, std::io::Error> {
+        read_table(&Self::get_file_name(dir), &Self::get_header())
+    }
+
+    pub fn map_table_from_file(dir: &Path) -> Result<MappedTable, std::io::Error> {
+        map_table(&Self::get_file_name(dir), &Self::get_header())
+    }
+
+    // Two Bits for each State
+    fn get_header() -> TableHeader<'static> {
+        TableHeader {
+            kind: TableKind::Pruning,
+            name: S::NAME,
+            entry_bits: 2,
+            count: S::SIZE as u64
+        }
+    }
+
+    pub fn get_file_name(dir: &Path) -> String {
+        get_table_file(dir, "pruning", &format!("{}.bin", S::NAME))
     }
 
-    pub fn get_file_name() -> String {
-        String::from_str("tables/pruning/").unwrap() + S::NAME + ".bin"
+    pub fn get_checkpoint_name(dir: &Path) -> String {
+        get_table_file(dir, "pruning", &format!("{}.checkpoint", S::NAME))
     }
 }
 
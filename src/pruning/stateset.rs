@@ -1,11 +1,22 @@
+use crate::{error::Error, tables::Tables};
+
 
 pub trait StateSet {
     const NAME: &str;
     const SIZE: usize;
+    // Depth of the deepest States of the generated Table, checked in tests/depths.rs
     const MAX_DEPTH: u8;
-    fn solved() -> Self;
+    // Tables the State Set reads, taken from the Tables once, so that reading them can't fail
+    type Lookup: Send + Sync;
+    fn lookup(tables: &Tables) -> Result<Self::Lookup, Error>;
+    fn solved(lookup: &Self::Lookup) -> Self;
     fn from_hash(hash: usize) -> Self;
     fn get_hash(&self) -> usize;
-    fn get_sym_hashes(&self) -> Vec<usize>;
-    fn get_next_state_sets(&self) -> Vec<Self> where Self: Sized;
-}
\ No newline at end of file
+    fn get_sym_hashes(&self, lookup: &Self::Lookup) -> Vec<usize>;
+    fn get_next_state_sets(&self, lookup: &Self::Lookup) -> Vec<Self> where Self: Sized;
+
+    // All State Sets at Depth 0, for Tables with more than one solved State
+    fn get_solved_state_sets(lookup: &Self::Lookup) -> Vec<Self> where Self: Sized {
+        vec![Self::solved(lookup)]
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{moving::turn::TurnTable, state::{statefto2::{StateFTO2, FTO2_SYMHASH_TABLE, FTO2_TURN_TABLE}, symhash::SymHash, State}};
+use std::sync::Arc;
+
+use crate::{error::Error, moving::turn::TurnTable, state::{statefto2::StateFTO2, symhash::SymHash, State}, tables::{SymTables, Tables}};
 
 use super::stateset::StateSet;
 
@@ -13,10 +15,15 @@ impl StateSet for StateSetFTO2 {
     const SIZE: usize = StateFTO2::SYM_SIZE;
 
     const MAX_DEPTH: u8 = 10;
+    type Lookup = Arc<SymTables>;
+
+    fn lookup(tables: &Tables) -> Result<Arc<SymTables>, Error> {
+        tables.fto2()
+    }
 
-    fn solved() -> Self {
+    fn solved(fto2: &Arc<SymTables>) -> Self {
         Self {
-            fto2: StateFTO2::solved().get_sym_hash(&FTO2_SYMHASH_TABLE)
+            fto2: StateFTO2::solved().get_sym_hash_indexed(fto2.symindex())
         }
     }
 
@@ -30,12 +37,12 @@ impl StateSet for StateSetFTO2 {
         self.fto2
     }
 
-    fn get_sym_hashes(&self) -> Vec<usize> {
+    fn get_sym_hashes(&self, _fto2: &Arc<SymTables>) -> Vec<usize> {
         vec![]
     }
 
-    fn get_next_state_sets(&self) -> Vec<Self> where Self: Sized {
-        StateFTO2::apply_turns_table(&FTO2_TURN_TABLE, self.fto2).iter().map(|&hash| Self{fto2: hash / StateFTO2::NUM_SYMS as usize}).collect()
-        // StateFTO2::from_sym_hash(&FTO2_SYMHASH_TABLE, self.fto2).get_next_states().iter().map(|fto2| Self {fto2: fto2.get_sym_hash(&FTO2_SYMHASH_TABLE)}).collect()
+    fn get_next_state_sets(&self, fto2: &Arc<SymTables>) -> Vec<Self> where Self: Sized {
+        StateFTO2::apply_turns_table(fto2.turn(), self.fto2).iter().map(|&hash| Self{fto2: hash / StateFTO2::NUM_SYMS as usize}).collect()
+        // StateFTO2::from_sym_hash(fto2.symhash(), self.fto2).get_next_states().iter().map(|next| Self {fto2: next.get_sym_hash(fto2.symhash())}).collect()
     }
 }
\ No newline at end of file
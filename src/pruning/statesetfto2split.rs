@@ -1,4 +1,6 @@
-use crate::{moving::{sym::SymTable, turn::TurnTable}, state::{statecenters::{StateCenters, CENTERS_SYM_TABLE, CENTERS_TURN_TABLE}, statecorners::{StateCorners, CORNERS_SYMHASH_TABLE, CORNERS_TURN_TABLE}, symhash::SymHash, State}};
+use std::sync::Arc;
+
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}, state::{statecenters::StateCenters, statecorners::StateCorners, symhash::SymHash, RawHash, State}, tables::{RawTables, SymTables, Tables}};
 
 use super::stateset::StateSet;
 
@@ -15,10 +17,16 @@ impl StateSet for StateSetFTO2Split {
     const SIZE: usize = StateCorners::SYM_SIZE * StateCenters::RAW_SIZE;
 
     const MAX_DEPTH: u8 = 10;
+    // Tables of the Corners and of the Centers
+    type Lookup = (Arc<SymTables>, Arc<RawTables>);
+
+    fn lookup(tables: &Tables) -> Result<Self::Lookup, Error> {
+        Ok((tables.corners()?, tables.centers()?))
+    }
 
-    fn solved() -> Self {
+    fn solved((corners, _): &Self::Lookup) -> Self {
         Self {
-            corners: StateCorners::solved().get_sym_hash(&CORNERS_SYMHASH_TABLE),
+            corners: StateCorners::solved().get_sym_hash_indexed(corners.symindex()),
             centers: StateCenters::solved().get_hash()
         }
     }
@@ -34,19 +42,19 @@ impl StateSet for StateSetFTO2Split {
         self.corners * StateCenters::RAW_SIZE + self.centers
     }
 
-    fn get_sym_hashes(&self) -> Vec<usize> {
-        StateCorners::from_sym_hash(&CORNERS_SYMHASH_TABLE, self.corners).get_syms().iter().map(|&sym|
-            self.corners * StateCenters::RAW_SIZE + StateCenters::apply_sym_table(&CENTERS_SYM_TABLE, self.centers, sym)
+    fn get_sym_hashes(&self, (corners, centers): &Self::Lookup) -> Vec<usize> {
+        StateCorners::from_sym_hash(corners.symhash(), self.corners).get_syms().iter().map(|&sym|
+            self.corners * StateCenters::RAW_SIZE + StateCenters::apply_sym_table(centers.sym(), self.centers, sym)
         ).collect()
     }
 
-    fn get_next_state_sets(&self) -> Vec<Self> where Self: Sized {
+    fn get_next_state_sets(&self, (corners, centers): &Self::Lookup) -> Vec<Self> where Self: Sized {
         (0..StateCorners::NUM_TURNS).map(|turn| {
-            let corners_comb_hash = StateCorners::apply_turn_table(&CORNERS_TURN_TABLE, self.corners, turn);
+            let corners_comb_hash = StateCorners::apply_turn_table(corners.turn(), self.corners, turn);
             let sym_hash = corners_comb_hash / StateCorners::NUM_SYMS as usize;
             let sym = (corners_comb_hash % StateCorners::NUM_SYMS as usize) as u8;
-            let centers_raw_hash = StateCenters::apply_turn_table(&CENTERS_TURN_TABLE, self.centers, turn);
-            let centers_hash = StateCenters::apply_sym_table(&CENTERS_SYM_TABLE, centers_raw_hash, sym);
+            let centers_raw_hash = StateCenters::apply_turn_table(centers.turn(), self.centers, turn);
+            let centers_hash = StateCenters::apply_sym_table(centers.sym(), centers_raw_hash, sym);
             Self {
                 corners: sym_hash,
                 centers: centers_hash
@@ -56,8 +64,8 @@ impl StateSet for StateSetFTO2Split {
 }
 
 impl StateSetFTO2Split {
-    pub fn from_parts(raw_cp_hash: usize, raw_co_hash: usize, raw_ce_hash: usize) -> Self {
-        let (corners_sym_hash, sym) = StateCorners::from_hash(raw_cp_hash * 32 + raw_co_hash).get_sym_hash_sym(&CORNERS_SYMHASH_TABLE);
+    pub fn from_parts(corners: &SymTables, raw_cp_hash: usize, raw_co_hash: usize, raw_ce_hash: usize) -> Self {
+        let (corners_sym_hash, sym) = StateCorners::from_hash(raw_cp_hash * 32 + raw_co_hash).get_sym_hash_sym_indexed(corners.symindex());
         let mut state_centers = StateCenters::from_hash(raw_ce_hash);
         state_centers.wrap_sym(sym);
         Self {
@@ -0,0 +1,58 @@
+use crate::{error::Error, state::{hash_centers, statefto::StateFTO, unhash_centers, State}, tables::Tables};
+
+use super::stateset::StateSet;
+
+
+// Centers of D, B, L and R, which are solved in the Pyra Subgroup
+pub struct StateSetFTOCenters {
+    ce2: usize
+}
+
+impl StateSet for StateSetFTOCenters {
+    const NAME: &str = "ftocenters";
+    const SIZE: usize = 369_600;
+    const MAX_DEPTH: u8 = 8;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            ce2: hash
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.ce2
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> where Self: Sized {
+        self.to_fto().get_next_states().iter().map(Self::from_fto).collect()
+    }
+}
+
+impl StateSetFTOCenters {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            ce2: hash_centers(&fto.ce2)
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        StateFTO {
+            ce2: unhash_centers(self.ce2),
+            ..StateFTO::solved()
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{error::Error, state::{hash_combination, hash_orientation, hash_permutation, statecorners::StateCorners, statefto::StateFTO, unhash_combination, RawHash, State}, tables::Tables};
+
+use super::stateset::StateSet;
+
+
+// Corners together with the Centers of one Color of U, F, BL and BR,
+// which are carried by the Corners in the Pyra Subgroup
+pub struct StateSetFTOCorners<const COLOR: u8> {
+    corners: usize,
+    centers: usize
+}
+
+impl<const COLOR: u8> StateSet for StateSetFTOCorners<COLOR> {
+    const NAME: &str = match COLOR {
+        0 => "ftocorners0",
+        1 => "ftocorners1",
+        2 => "ftocorners2",
+        _ => "ftocorners3"
+    };
+    const SIZE: usize = StateCorners::RAW_SIZE * 220;
+    const MAX_DEPTH: u8 = 5;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            corners: hash / 220,
+            centers: hash % 220
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.corners * 220 + self.centers
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> where Self: Sized {
+        self.to_fto().get_next_states().iter().map(Self::from_fto).collect()
+    }
+
+    fn get_solved_state_sets(_lookup: &()) -> Vec<Self> where Self: Sized {
+        // Every Corner State with its Centers carried along
+        (0..StateCorners::RAW_SIZE).map(|hash| {
+            let corners = StateCorners::from_hash(hash);
+            let mut fto = StateFTO {
+                cp: corners.cp,
+                co: corners.co,
+                ..StateFTO::solved()
+            };
+            fto.apply_permutation_ce1(corners.cp, corners.co);
+            Self::from_fto(&fto)
+        }).collect()
+    }
+}
+
+impl<const COLOR: u8> StateSetFTOCorners<COLOR> {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            corners: hash_permutation(&fto.cp) * 32 + hash_orientation(&fto.co),
+            centers: hash_combination(&fto.ce1.map(|center| center == COLOR))
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        let corners = StateCorners::from_hash(self.corners);
+        StateFTO {
+            cp: corners.cp,
+            co: corners.co,
+            ce1: unhash_combination::<12>(self.centers, 3).map(|set| if set {COLOR} else {4}),
+            ..StateFTO::solved()
+        }
+    }
+}
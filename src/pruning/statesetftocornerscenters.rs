@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}, state::{statecorners::StateCorners, statefto::StateFTO, stateftocenters::StateFTOCenters, stateftocorners::StateFTOCorners, symhash::SymHash, RawHash, State}, tables::{RawTables, SymTables, Tables}};
+
+use super::stateset::StateSet;
+
+
+// Corners together with the Centers of one Orbit, ce1 for ORBIT 1 and ce2 for ORBIT 2
+// The Corners are reduced by the Syms without x2, which keep the Orbit,
+// and those Syms of the Corners are the Syms of the FTO2 Corners
+// The Corners are turned by the Turn Table of the FTO Corners and reduced by their SymHash Index
+pub struct StateSetFTOCornersCenters<const ORBIT: u8> {
+    corners: usize,
+    centers: usize
+}
+
+impl<const ORBIT: u8> StateSet for StateSetFTOCornersCenters<ORBIT> {
+    const NAME: &str = if ORBIT == 1 {"ftocornersce1"} else {"ftocornersce2"};
+    const SIZE: usize = StateCorners::SYM_SIZE * StateFTOCenters::<ORBIT>::RAW_SIZE;
+    const MAX_DEPTH: u8 = 12;
+    // SymHash Tables of the Corners, Tables of the FTO Corners and of the Centers
+    type Lookup = (Arc<SymTables>, Arc<RawTables>, Arc<RawTables>);
+
+    fn lookup(tables: &Tables) -> Result<Self::Lookup, Error> {
+        Ok((tables.corners()?, tables.fto_corners()?, tables.fto_centers::<ORBIT>()?))
+    }
+
+    fn solved((corners, _, _): &Self::Lookup) -> Self {
+        Self::from_fto(corners, &StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            corners: hash / StateFTOCenters::<ORBIT>::RAW_SIZE,
+            centers: hash % StateFTOCenters::<ORBIT>::RAW_SIZE
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.corners * StateFTOCenters::<ORBIT>::RAW_SIZE + self.centers
+    }
+
+    fn get_sym_hashes(&self, (corners, _, centers): &Self::Lookup) -> Vec<usize> {
+        StateCorners::from_sym_hash(corners.symhash(), self.corners).get_syms().iter().map(|&sym|
+            self.corners * StateFTOCenters::<ORBIT>::RAW_SIZE + StateFTOCenters::<ORBIT>::apply_sym_table(centers.sym(), self.centers, sym << 1)
+        ).collect()
+    }
+
+    fn get_next_state_sets(&self, (corners, fto_corners, centers): &Self::Lookup) -> Vec<Self> where Self: Sized {
+        let corners_raw_hash = corners.symhash()[self.corners] as usize;
+        (0..StateFTO::NUM_TURNS).map(|turn| {
+            let turned_corners = StateFTOCorners::apply_turn_table(fto_corners.turn(), corners_raw_hash, turn);
+            let (sym_hash, sym) = StateCorners::from_hash(turned_corners).get_sym_hash_sym_indexed(corners.symindex());
+            let centers_raw_hash = StateFTOCenters::<ORBIT>::apply_turn_table(centers.turn(), self.centers, turn);
+            Self {
+                corners: sym_hash,
+                centers: StateFTOCenters::<ORBIT>::apply_sym_table(centers.sym(), centers_raw_hash, sym << 1)
+            }
+        }).collect()
+    }
+}
+
+impl<const ORBIT: u8> StateSetFTOCornersCenters<ORBIT> {
+    pub fn from_fto(corners: &SymTables, fto: &StateFTO) -> Self {
+        let (sym_hash, sym) = StateCorners::from_hash(StateFTOCorners::from_fto(fto).get_hash()).get_sym_hash_sym_indexed(corners.symindex());
+        let mut centers = StateFTOCenters::<ORBIT>::from_fto(fto);
+        centers.wrap_sym(sym << 1);
+        Self {
+            corners: sym_hash,
+            centers: centers.get_hash()
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{error::Error, state::{hash_centers, statefto::StateFTO, stateftoedgetrio::{EDGE_TRIOS, MAP_EP_TRIO}, unhash_centers, State}, tables::Tables};
+
+use super::stateset::StateSet;
+
+
+// Edges, which are cycled inside their Trio in the Pyra Subgroup
+pub struct StateSetFTOEdges {
+    ep: usize
+}
+
+impl StateSet for StateSetFTOEdges {
+    const NAME: &str = "ftoedges";
+    const SIZE: usize = 369_600 * 16;
+    const MAX_DEPTH: u8 = 12;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            ep: hash
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.ep
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> where Self: Sized {
+        self.to_fto().get_next_states().iter().map(Self::from_fto).collect()
+    }
+}
+
+impl StateSetFTOEdges {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        let trios = fto.ep.map(|edge| MAP_EP_TRIO[edge as usize]);
+        // Parity of the Edges inside each Trio, since only 3-cycles are allowed
+        let mut odd = [false; 4];
+        let mut previous = [[12; 2]; 4];
+        for edge in fto.ep {
+            let trio = MAP_EP_TRIO[edge as usize] as usize;
+            odd[trio] ^= (previous[trio][0] < 12 && previous[trio][0] > edge) ^ (previous[trio][1] < 12 && previous[trio][1] > edge);
+            previous[trio] = [edge, previous[trio][0]];
+        }
+        let parities = odd.iter().fold(0, |acc, &odd| acc * 2 + odd as usize);
+        Self {
+            ep: hash_centers(&trios) * 16 + parities
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        let trios = unhash_centers(self.ep / 16);
+        let mut ep = [12; 12];
+        let mut placed = [0; 4];
+        let mut first = [12; 4];
+        for index in 0..12 {
+            let trio = trios[index] as usize;
+            ep[index] = EDGE_TRIOS[trio][placed[trio]];
+            if placed[trio] == 0 {
+                first[trio] = index;
+            } else if placed[trio] == 1 && (self.ep >> (3 - trio)) & 1 == 1 {
+                ep.swap(first[trio], index);
+            }
+            placed[trio] += 1;
+        }
+        StateFTO {
+            ep,
+            ..StateFTO::solved()
+        }
+    }
+}
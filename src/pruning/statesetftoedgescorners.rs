@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use crate::{error::Error, moving::turn::TurnTable, state::{statefto::StateFTO, stateftocorners::StateFTOCorners, stateftoedgetrio::StateFTOEdgeTrio, RawHash, State}, tables::{RawTables, Tables}};
+
+use super::stateset::StateSet;
+
+
+// Corners together with the Edges of one Trio, the Edges turned by D, B, L or R
+// Both are turned by the Turn Tables of the FTO Coordinates
+pub struct StateSetFTOEdgesCorners<const TRIO: u8> {
+    corners: usize,
+    edges: usize
+}
+
+impl<const TRIO: u8> StateSet for StateSetFTOEdgesCorners<TRIO> {
+    const NAME: &str = match TRIO {
+        0 => "ftoedgescorners0",
+        1 => "ftoedgescorners1",
+        2 => "ftoedgescorners2",
+        _ => "ftoedgescorners3"
+    };
+    const SIZE: usize = StateFTOCorners::RAW_SIZE * StateFTOEdgeTrio::<TRIO>::RAW_SIZE;
+    const MAX_DEPTH: u8 = 10;
+    // Tables of the Corners and of the Edges
+    type Lookup = (Arc<RawTables>, Arc<RawTables>);
+
+    fn lookup(tables: &Tables) -> Result<Self::Lookup, Error> {
+        Ok((tables.fto_corners()?, tables.fto_edges::<TRIO>()?))
+    }
+
+    fn solved(_lookup: &Self::Lookup) -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            corners: hash / StateFTOEdgeTrio::<TRIO>::RAW_SIZE,
+            edges: hash % StateFTOEdgeTrio::<TRIO>::RAW_SIZE
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.corners * StateFTOEdgeTrio::<TRIO>::RAW_SIZE + self.edges
+    }
+
+    fn get_sym_hashes(&self, _lookup: &Self::Lookup) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, (corners, edges): &Self::Lookup) -> Vec<Self> where Self: Sized {
+        (0..StateFTO::NUM_TURNS).map(|turn| Self {
+            corners: StateFTOCorners::apply_turn_table(corners.turn(), self.corners, turn),
+            edges: StateFTOEdgeTrio::<TRIO>::apply_turn_table(edges.turn(), self.edges, turn)
+        }).collect()
+    }
+}
+
+impl<const TRIO: u8> StateSetFTOEdgesCorners<TRIO> {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            corners: StateFTOCorners::from_fto(fto).get_hash(),
+            edges: StateFTOEdgeTrio::<TRIO>::from_fto(fto).get_hash()
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{error::Error, state::{hash_centers, statefto::StateFTO, stateftoedgetrio::{EDGE_TRIOS, MAP_EP_TRIO}, unhash_centers, State}, tables::Tables};
+
+use super::stateset::StateSet;
+
+
+// Positions and Trios read by StatePyra::from_fto
+const SUBGROUP_POSITIONS: [usize; 4] = [4, 1, 0, 2];
+
+// Trios of the Edges on all Positions, which decide whether StatePyra::from_fto succeeds:
+// it does, once the Positions it reads hold an Edge of their own Trio
+pub struct StateSetFTOSubgroup {
+    trios: usize
+}
+
+impl StateSet for StateSetFTOSubgroup {
+    const NAME: &str = "ftosubgroup";
+    const SIZE: usize = 369_600;
+    const MAX_DEPTH: u8 = 6;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            trios: hash
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.trios
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> where Self: Sized {
+        self.to_fto().get_next_states().iter().map(Self::from_fto).collect()
+    }
+
+    fn get_solved_state_sets(_lookup: &()) -> Vec<Self> where Self: Sized {
+        // Every Distribution of the other Edges
+        (0..Self::SIZE).map(Self::from_hash).filter(Self::is_solved).collect()
+    }
+}
+
+impl StateSetFTOSubgroup {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            trios: hash_centers(&fto.ep.map(|edge| MAP_EP_TRIO[edge as usize]))
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        let trios = unhash_centers(self.trios);
+        SUBGROUP_POSITIONS.iter().enumerate().all(|(trio, &position)| trios[position] as usize == trio)
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        let mut placed = [0; 4];
+        let ep = unhash_centers(self.trios).map(|trio| {
+            placed[trio as usize] += 1;
+            EDGE_TRIOS[trio as usize][placed[trio as usize] - 1]
+        });
+        StateFTO {
+            ep,
+            ..StateFTO::solved()
+        }
+    }
+}
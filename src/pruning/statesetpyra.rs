@@ -1,4 +1,6 @@
-use crate::{moving::turn::TurnTable, state::{statepyra::{StatePyra, PYRA_SYMHASH_TABLE, PYRA_TURN_TABLE}, symhash::SymHash, State}};
+use std::sync::Arc;
+
+use crate::{error::Error, moving::turn::TurnTable, state::{statepyra::StatePyra, symhash::SymHash, State}, tables::{SymTables, Tables}};
 
 use super::stateset::StateSet;
 
@@ -11,10 +13,15 @@ impl StateSet for StateSetPyra {
     const NAME: &str = "pyra";
     const SIZE: usize = StatePyra::SYM_SIZE;
     const MAX_DEPTH: u8 = 11;
+    type Lookup = Arc<SymTables>;
+
+    fn lookup(tables: &Tables) -> Result<Arc<SymTables>, Error> {
+        tables.pyra()
+    }
 
-    fn solved() -> Self {
+    fn solved(pyra: &Arc<SymTables>) -> Self {
         Self {
-            pyra: StatePyra::solved().get_sym_hash(&PYRA_SYMHASH_TABLE)
+            pyra: StatePyra::solved().get_sym_hash_indexed(pyra.symindex())
         }
     }
 
@@ -28,11 +35,11 @@ impl StateSet for StateSetPyra {
         self.pyra
     }
 
-    fn get_sym_hashes(&self) -> Vec<usize> {
+    fn get_sym_hashes(&self, _pyra: &Arc<SymTables>) -> Vec<usize> {
         vec![]
     }
 
-    fn get_next_state_sets(&self) -> Vec<Self> where Self: Sized {
-        StatePyra::apply_turns_table(&PYRA_TURN_TABLE, self.pyra).iter().map(|&hash| Self{pyra: hash / StatePyra::NUM_SYMS as usize}).collect()
+    fn get_next_state_sets(&self, pyra: &Arc<SymTables>) -> Vec<Self> where Self: Sized {
+        StatePyra::apply_turns_table(pyra.turn(), self.pyra).iter().map(|&hash| Self{pyra: hash / StatePyra::NUM_SYMS as usize}).collect()
     }
 }
\ No newline at end of file
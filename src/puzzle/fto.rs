@@ -1,32 +1,12 @@
 use std::fmt;
 
-use crate::state::{statefto::StateFTO, unwrap_turn, State};
+use crate::state::{statefto::StateFTO, unwrap_turn};
 
 use super::Puzzle;
 
 
 impl Puzzle for StateFTO {
-    fn do_notation(&mut self, _notation: &str) {
-        match _notation {
-            "D" => self.apply_turn(0),
-            "D'" => self.apply_turn(1),
-            "B" => self.apply_turn(2),
-            "B'" => self.apply_turn(3),
-            "L" => self.apply_turn(4),
-            "L'" => self.apply_turn(5),
-            "R" => self.apply_turn(6),
-            "R'" => self.apply_turn(7),
-            "U" => self.apply_turn(8),
-            "U'" => self.apply_turn(9),
-            "F" => self.apply_turn(10),
-            "F'" => self.apply_turn(11),
-            "BL" => self.apply_turn(12),
-            "BL'" => self.apply_turn(13),
-            "BR" => self.apply_turn(14),
-            "BR'" => self.apply_turn(15),
-            _ => println!("Didn't execute turn")
-        }
-    }
+    const NUM_MOVES: u8 = 16;
 
     fn get_notation(turn: u8) -> &'static str {
         let (face, cw) = unwrap_turn(turn);
@@ -42,6 +22,10 @@ impl Puzzle for StateFTO {
             _ => ""
         }
     }
+
+    fn faces_commute(face: u8, other: u8) -> bool {
+        StateFTO::faces_commute(face, other)
+    }
 }
 
 /*
@@ -2,25 +2,13 @@ use std::fmt;
 
 use colored::Colorize;
 
-use crate::state::{statefto2::StateFTO2, unwrap_turn, State};
+use crate::state::{statefto2::StateFTO2, unwrap_turn};
 
 use super::Puzzle;
 
 
 impl Puzzle for StateFTO2 {
-    fn do_notation(&mut self, _notation: &str) {
-        match _notation {
-            "D" => self.apply_turn(0),
-            "D'" => self.apply_turn(1),
-            "B" => self.apply_turn(2),
-            "B'" => self.apply_turn(3),
-            "L" => self.apply_turn(4),
-            "L'" => self.apply_turn(5),
-            "R" => self.apply_turn(6),
-            "R'" => self.apply_turn(7),
-            _ => println!("Didn't execute turn")
-        }
-    }
+    const NUM_MOVES: u8 = 8;
 
     fn get_notation(turn: u8) -> &'static str {
         let (face, cw) = unwrap_turn(turn);
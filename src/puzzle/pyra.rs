@@ -0,0 +1,27 @@
+use std::fmt;
+
+use crate::state::{statefto::StateFTO, statepyra::StatePyra, unwrap_turn};
+
+use super::Puzzle;
+
+
+impl Puzzle for StatePyra {
+    const NUM_MOVES: u8 = 8;
+
+    fn get_notation(turn: u8) -> &'static str {
+        let (face, cw) = unwrap_turn(turn);
+        match face {
+            0 => if cw {"D"} else {"D'"},
+            1 => if cw {"B"} else {"B'"},
+            2 => if cw {"L"} else {"L'"},
+            3 => if cw {"R"} else {"R'"},
+            _ => ""
+        }
+    }
+}
+
+impl fmt::Display for StatePyra {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", StateFTO::from_pyra(self))
+    }
+}
\ No newline at end of file
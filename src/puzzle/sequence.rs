@@ -0,0 +1,338 @@
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use crate::state::unwrap_turn;
+
+use super::Puzzle;
+
+
+// A single Turn of the Puzzle P
+pub struct Move<P: Puzzle> {
+    turn: u8,
+    _marker: PhantomData<P>
+}
+
+impl<P: Puzzle> Move<P> {
+    // Only for Turns of the Puzzle, Moves from outside are parsed
+    pub(crate) fn new(turn: u8) -> Self {
+        assert!(turn < P::NUM_MOVES, "Turn {turn} out of range");
+        Self {
+            turn,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn turn(&self) -> u8 {
+        self.turn
+    }
+
+    pub fn face(&self) -> u8 {
+        unwrap_turn(self.turn).0
+    }
+
+    pub fn is_clockwise(&self) -> bool {
+        unwrap_turn(self.turn).1
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self::new(self.turn ^ 1)
+    }
+}
+
+impl<P: Puzzle> Clone for Move<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Puzzle> Copy for Move<P> {}
+
+impl<P: Puzzle> PartialEq for Move<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.turn == other.turn
+    }
+}
+
+impl<P: Puzzle> Eq for Move<P> {}
+
+impl<P: Puzzle> fmt::Debug for Move<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Move({})", P::get_notation(self.turn))
+    }
+}
+
+impl<P: Puzzle> fmt::Display for Move<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", P::get_notation(self.turn))
+    }
+}
+
+impl<P: Puzzle> FromStr for Move<P> {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match (0..P::NUM_MOVES).find(|&turn| P::get_notation(turn) == s) {
+            Some(turn) => Ok(Self::new(turn)),
+            None => Err(ParseMoveError {
+                token: s.to_string()
+            })
+        }
+    }
+}
+
+// A Sequence of Turns of the Puzzle P, written with Moves separated by whitespace
+pub struct Sequence<P: Puzzle> {
+    moves: Vec<Move<P>>
+}
+
+impl<P: Puzzle> Sequence<P> {
+    pub fn new() -> Self {
+        Self {
+            moves: vec![]
+        }
+    }
+
+    // Only for Turns of the Puzzle, Sequences from outside are parsed
+    pub(crate) fn from_turns(turns: &[u8]) -> Self {
+        Self {
+            moves: turns.iter().map(|&turn| Move::new(turn)).collect()
+        }
+    }
+
+    pub fn moves(&self) -> &[Move<P>] {
+        &self.moves
+    }
+
+    pub fn turns(&self) -> Vec<u8> {
+        self.moves.iter().map(|mv| mv.turn).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn push(&mut self, mv: Move<P>) {
+        self.moves.push(mv);
+    }
+
+    // Undoes the Sequence
+    pub fn inverse(&self) -> Self {
+        Self {
+            moves: self.moves.iter().rev().map(|mv| mv.inverse()).collect()
+        }
+    }
+
+    // This Sequence followed by the other one
+    pub fn concat(&self, other: &Self) -> Self {
+        Self {
+            moves: self.moves.iter().chain(&other.moves).copied().collect()
+        }
+    }
+
+    // Shortest equivalent Sequence, using that every Face has order 3 and that commuting Faces can be swapped
+    pub fn simplified(&self) -> Self {
+        // Groups of commuting Faces, each with its amount of clockwise Turns
+        let mut groups: Vec<Vec<(u8, u8)>> = vec![];
+        for mv in &self.moves {
+            groups.push(vec![(mv.face(), if mv.is_clockwise() {1} else {2})]);
+            // Merges the last Group into the one before, while all their Faces commute
+            while groups.len() > 1 && groups[groups.len() - 1].iter().all(|&(face, _)| {
+                groups[groups.len() - 2].iter().all(|&(other, _)| other == face || P::faces_commute(face, other))
+            }) {
+                let group = groups.pop().unwrap();
+                let before = groups.last_mut().unwrap();
+                for (face, amount) in group {
+                    match before.iter().position(|&(other, _)| other == face) {
+                        Some(index) => before[index].1 = (before[index].1 + amount) % 3,
+                        None => before.push((face, amount))
+                    }
+                }
+                before.retain(|&(_, amount)| amount != 0);
+            }
+            if groups.last().is_some_and(|group| group.is_empty()) {
+                groups.pop();
+            }
+        }
+        let mut turns = vec![];
+        for mut group in groups {
+            group.sort();
+            turns.extend(group.iter().map(|&(face, amount)| face * 2 + amount - 1));
+        }
+        Self::from_turns(&turns)
+    }
+}
+
+impl<P: Puzzle> Default for Sequence<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Puzzle> Clone for Sequence<P> {
+    fn clone(&self) -> Self {
+        Self {
+            moves: self.moves.clone()
+        }
+    }
+}
+
+impl<P: Puzzle> PartialEq for Sequence<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.moves == other.moves
+    }
+}
+
+impl<P: Puzzle> Eq for Sequence<P> {}
+
+impl<P: Puzzle> fmt::Debug for Sequence<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sequence({self})")
+    }
+}
+
+impl<P: Puzzle> fmt::Display for Sequence<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let notations: Vec<&str> = self.moves.iter().map(|mv| P::get_notation(mv.turn)).collect();
+        write!(f, "{}", notations.join(" "))
+    }
+}
+
+impl<P: Puzzle> FromStr for Sequence<P> {
+    type Err = ParseSequenceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let moves = s.split_whitespace().enumerate().map(|(position, token)| {
+            token.parse().map_err(|err: ParseMoveError| ParseSequenceError {
+                token: err.token,
+                position
+            })
+        }).collect::<Result<Vec<Move<P>>, ParseSequenceError>>()?;
+        Ok(Self {
+            moves
+        })
+    }
+}
+
+impl<P: Puzzle> From<Move<P>> for Sequence<P> {
+    fn from(mv: Move<P>) -> Self {
+        Self {
+            moves: vec![mv]
+        }
+    }
+}
+
+impl<'a, P: Puzzle> IntoIterator for &'a Sequence<P> {
+    type Item = &'a Move<P>;
+    type IntoIter = std::slice::Iter<'a, Move<P>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.iter()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMoveError {
+    pub token: String
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown move \"{}\"", self.token)
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+// Position counts the Moves of the Sequence, starting at 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSequenceError {
+    pub token: String,
+    pub position: usize
+}
+
+impl fmt::Display for ParseSequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown move \"{}\" at position {}", self.token, self.position)
+    }
+}
+
+impl std::error::Error for ParseSequenceError {}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::state::{statefto::StateFTO, State};
+
+    use super::*;
+
+    #[test]
+    fn every_move_is_parsed_from_its_notation() {
+        for turn in 0..StateFTO::NUM_MOVES {
+            let mv: Move<StateFTO> = StateFTO::get_notation(turn).parse().unwrap();
+            assert_eq!(mv.turn(), turn);
+            assert_eq!(mv.to_string(), StateFTO::get_notation(turn));
+        }
+    }
+
+    #[test]
+    fn sequence_is_displayed_as_parsed() {
+        let sequence: Sequence<StateFTO> = " R  U'\tBL BR' D\n".parse().unwrap();
+        assert_eq!(sequence.turns(), [6, 9, 12, 15, 0]);
+        assert_eq!(sequence.to_string(), "R U' BL BR' D");
+        assert_eq!(sequence.to_string().parse::<Sequence<StateFTO>>().unwrap(), sequence);
+        assert!("".parse::<Sequence<StateFTO>>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unknown_move_has_its_position() {
+        let err = "R U X F".parse::<Sequence<StateFTO>>().unwrap_err();
+        assert_eq!(err, ParseSequenceError { token: String::from("X"), position: 2 });
+        assert_eq!(err.to_string(), "Unknown move \"X\" at position 2");
+        assert_eq!("R2".parse::<Move<StateFTO>>().unwrap_err().token, "R2");
+    }
+
+    fn simplified(notation: &str) -> String {
+        notation.parse::<Sequence<StateFTO>>().unwrap().simplified().to_string()
+    }
+
+    #[test]
+    fn turns_of_a_face_are_merged() {
+        assert_eq!(simplified("R R R"), "");
+        assert_eq!(simplified("R R"), "R'");
+        assert_eq!(simplified("R' R' U"), "R U");
+        assert_eq!(simplified("R U U' R'"), "");
+    }
+
+    #[test]
+    fn commuting_faces_are_merged() {
+        assert_eq!(simplified("U D U'"), "D");
+        assert_eq!(simplified("U D U"), "D U'");
+        assert_eq!(simplified("R L R'"), "R L R'");
+    }
+
+    #[test]
+    fn simplified_sequence_turns_the_same() {
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..200 {
+            let turns: Vec<u8> = (0..20).map(|_| rng.random_range(0..StateFTO::NUM_MOVES)).collect();
+            let sequence = Sequence::<StateFTO>::from_turns(&turns);
+            let simplified = sequence.simplified();
+            assert!(simplified.len() <= sequence.len());
+            let (mut fto, mut simplified_fto) = (StateFTO::solved(), StateFTO::solved());
+            fto.do_sequence(&sequence);
+            simplified_fto.do_sequence(&simplified);
+            assert_eq!(fto.to_facelets(), simplified_fto.to_facelets(), "{sequence}");
+        }
+    }
+
+    #[test]
+    fn failed_notation_turns_nothing() {
+        let mut fto = StateFTO::solved();
+        assert!(fto.do_notation("R U X").is_err());
+        assert_eq!(fto.to_facelets(), StateFTO::solved().to_facelets());
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{error::Error, puzzle::Puzzle, state::State};
+
+
+pub trait Heuristic<S: State> {
+    // Lower Bound of the Turns needed to solve the State
+    fn get_bound(&self, state: &S) -> u8;
+
+    // Lower Bound of a State, which was reached by one Turn from a State with the given Bound
+    fn get_next_bound(&self, next: &S, _bound: u8) -> u8 {
+        self.get_bound(next)
+    }
+}
+
+#[derive(Clone)]
+pub struct IdaStar<'a, S: Puzzle> {
+    heuristics: Vec<&'a dyn Heuristic<S>>,
+    num_turns: u8
+}
+
+impl<'a, S: Puzzle> IdaStar<'a, S> {
+    // Searches with the Turns 0 to num_turns until all Heuristics are 0
+    pub fn new(heuristics: Vec<&'a dyn Heuristic<S>>, num_turns: u8) -> Self {
+        Self {
+            heuristics,
+            num_turns
+        }
+    }
+
+    // Fails, if the Heuristics never reach 0 together
+    pub fn solve(&self, state: &S) -> Result<Vec<u8>, Error> {
+        self.solve_after(state, None)
+    }
+
+    // Solves the State without starting on the Face of the last Turn
+    pub fn solve_after(&self, state: &S, last_turn: Option<u8>) -> Result<Vec<u8>, Error> {
+        self.solutions_after(state, last_turn).next().ok_or(Error::NoSolution)
+    }
+
+    // All Solutions ordered by length, without redundant Turns and without passing the solved State
+    pub fn solutions(&self, state: &S) -> Solutions<'a, S> {
+        self.solutions_after(state, None)
+    }
+
+    pub fn solutions_after(&self, state: &S, last_turn: Option<u8>) -> Solutions<'a, S> {
+        let bounds: Vec<u8> = self.heuristics.iter().map(|heuristic| heuristic.get_bound(state)).collect();
+        Solutions {
+            ida_star: self.clone(),
+            max_depth: bounds.iter().copied().max().unwrap_or(0),
+            root: (state.clone(), bounds),
+            last_face: last_turn.map(|turn| turn >> 1),
+            started: false,
+            stack: vec![],
+            turns: vec![]
+        }
+    }
+}
+
+pub struct Solutions<'a, S: Puzzle> {
+    ida_star: IdaStar<'a, S>,
+    root: (S, Vec<u8>),
+    last_face: Option<u8>,
+    max_depth: u8,
+    started: bool,
+    // Depth first Search over the States with their Bounds and the next Turn to try
+    stack: Vec<(S, Vec<u8>, u8)>,
+    turns: Vec<u8>
+}
+
+impl<S: Puzzle> Iterator for Solutions<'_, S> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Starts the next Depth, after all Sequences of the current one are searched
+            if self.stack.is_empty() {
+                if self.started {
+                    if self.max_depth == u8::MAX || self.root.1.iter().all(|&bound| bound == 0) {
+                        return None;
+                    }
+                    self.max_depth += 1;
+                }
+                self.started = true;
+                let (root, bounds) = self.root.clone();
+                if let Some(solution) = self.open(root, bounds) {
+                    return Some(solution);
+                }
+                continue;
+            }
+
+            let top = self.stack.len() - 1;
+            let turn = self.stack[top].2;
+            if turn >= self.ida_star.num_turns {
+                self.close();
+                continue;
+            }
+            self.stack[top].2 += 1;
+
+            let face = turn >> 1;
+            let last_face = self.turns.last().map(|last_turn| last_turn >> 1).or(self.last_face);
+            // Skips turning the same Face twice and the order of commuting Faces
+            if last_face.is_some_and(|last_face| face == last_face || (face < last_face && S::faces_commute(face, last_face))) {
+                continue;
+            }
+            let (state, bounds, _) = &self.stack[top];
+            let mut next = state.clone();
+            next.apply_turn(turn);
+            // Stops at the first Heuristic exceeding the Depth
+            let remaining = self.max_depth - top as u8;
+            let mut next_bounds = Vec::with_capacity(bounds.len());
+            let in_bound = self.ida_star.heuristics.iter().zip(bounds).all(|(heuristic, &bound)| {
+                next_bounds.push(heuristic.get_next_bound(&next, bound));
+                next_bounds[next_bounds.len() - 1] < remaining
+            });
+            if !in_bound {
+                continue;
+            }
+            self.turns.push(turn);
+            if let Some(solution) = self.open(next, next_bounds) {
+                return Some(solution);
+            }
+        }
+    }
+}
+
+impl<S: Puzzle> Solutions<'_, S> {
+    // Pushes the State and returns the Turns, if it is solved at the full Depth
+    fn open(&mut self, state: S, bounds: Vec<u8>) -> Option<Vec<u8>> {
+        let solved = bounds.iter().all(|&bound| bound == 0);
+        let at_max = self.turns.len() == self.max_depth as usize;
+        self.stack.push((state, bounds, 0));
+        if solved || at_max {
+            let solution = if solved && at_max {Some(self.turns.clone())} else {None};
+            self.close();
+            solution
+        } else {
+            None
+        }
+    }
+
+    fn close(&mut self) {
+        self.stack.pop();
+        if !self.stack.is_empty() {
+            self.turns.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::state::statefto::StateFTO;
+
+    use super::*;
+
+    const DEPTH: u8 = 3;
+
+    // Exact Distances of all States up to the Depth, searched without skipping any Turns
+    struct Distances(HashMap<String, u8>);
+
+    impl Distances {
+        fn new() -> (Self, Vec<StateFTO>) {
+            let mut distances = HashMap::from([(StateFTO::solved().to_facelets(), 0)]);
+            let mut states = vec![StateFTO::solved()];
+            let mut closed = states.clone();
+            for distance in 1..=DEPTH {
+                closed = closed.iter().flat_map(StateFTO::get_next_states).filter(|next| {
+                    let facelets = next.to_facelets();
+                    !distances.contains_key(&facelets) && distances.insert(facelets, distance).is_none()
+                }).collect();
+                states.extend(closed.iter().cloned());
+            }
+            (Self(distances), states)
+        }
+    }
+
+    // States outside are further away than the Depth
+    impl Heuristic<StateFTO> for Distances {
+        fn get_bound(&self, state: &StateFTO) -> u8 {
+            self.0.get(&state.to_facelets()).copied().unwrap_or(DEPTH + 1)
+        }
+    }
+
+    // Skipping the Order of commuting Faces only drops Sequences, which have an equally long one
+    #[test]
+    fn commute_skip_keeps_optimal_lengths() {
+        let (distances, states) = Distances::new();
+        let ida_star = IdaStar::new(vec![&distances], StateFTO::NUM_TURNS);
+        for mut fto in states {
+            let distance = distances.get_bound(&fto);
+            let solution = ida_star.solve(&fto).unwrap();
+            assert_eq!(solution.len() as u8, distance);
+            solution.iter().for_each(|&turn| fto.apply_turn(turn));
+            assert_eq!(distances.get_bound(&fto), 0);
+        }
+    }
+}
@@ -1,4 +1,7 @@
+pub mod idastar;
+pub mod solverfto;
 pub mod solverfto2;
 pub mod solverfto2split;
+pub mod solverftosubgroup;
 
 pub mod solverpyra;
\ No newline at end of file
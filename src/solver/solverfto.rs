@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::{pruning::{heuristic::PruningHeuristic, stateset::StateSet, statesetftocenters::StateSetFTOCenters, statesetftocorners::StateSetFTOCorners, statesetftoedges::StateSetFTOEdges}, error::Error, puzzle::sequence::Sequence, tables::Tables, state::{statefto::StateFTO, statepyra::StatePyra, State}};
+
+use super::{idastar::{Heuristic, IdaStar}, solverpyra::SolverPyra};
+
+
+pub struct SolverFTO {
+    heuristics: Vec<Box<dyn Heuristic<StateFTO> + Send + Sync>>,
+    solver_pyra: SolverPyra
+}
+
+impl SolverFTO {
+    pub fn new(tables: Arc<Tables>) -> Result<Self, Error> {
+        let edges: PruningHeuristic<StateFTO, StateSetFTOEdges> = PruningHeuristic::load(tables.clone(), StateFTO::NUM_TURNS, |_, fto| StateSetFTOEdges::from_fto(fto).get_hash())?;
+        let centers: PruningHeuristic<StateFTO, StateSetFTOCenters> = PruningHeuristic::load(tables.clone(), StateFTO::NUM_TURNS, |_, fto| StateSetFTOCenters::from_fto(fto).get_hash())?;
+        let corners0: PruningHeuristic<StateFTO, StateSetFTOCorners<0>> = PruningHeuristic::load(tables.clone(), StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<0>::from_fto(fto).get_hash())?;
+        let corners1: PruningHeuristic<StateFTO, StateSetFTOCorners<1>> = PruningHeuristic::load(tables.clone(), StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<1>::from_fto(fto).get_hash())?;
+        let corners2: PruningHeuristic<StateFTO, StateSetFTOCorners<2>> = PruningHeuristic::load(tables.clone(), StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<2>::from_fto(fto).get_hash())?;
+        Ok(Self::with_heuristics(vec![Box::new(edges), Box::new(centers), Box::new(corners0), Box::new(corners1), Box::new(corners2)], SolverPyra::new(tables)?))
+    }
+
+    // Heuristics of the Stages in their Order, the last Stage has to reach the Pyra Subgroup
+    pub fn with_heuristics(heuristics: Vec<Box<dyn Heuristic<StateFTO> + Send + Sync>>, solver_pyra: SolverPyra) -> Self {
+        Self {
+            heuristics,
+            solver_pyra
+        }
+    }
+
+    // Fails for States, which can't be solved
+    pub fn solve(&self, fto: &StateFTO) -> Result<Sequence<StateFTO>, Error> {
+        fto.validate()?;
+
+        // Reduces the FTO into the Pyra Subgroup one Table at a time,
+        // while keeping the Tables of the earlier Stages solved:
+        // Edges, Centers, then the Corners with the Centers of three Colors
+        // Three Colors are enough, since the Centers of the last Color follow
+        let heuristics: Vec<&dyn Heuristic<StateFTO>> = self.heuristics.iter().map(|heuristic| &**heuristic as &dyn Heuristic<StateFTO>).collect();
+        let mut current = fto.clone();
+        let mut turns: Vec<u8> = vec![];
+        for stage in 1..=heuristics.len() {
+            let mut stage_turns = IdaStar::new(heuristics[..stage].to_vec(), StateFTO::NUM_TURNS).solve(&current)?;
+            stage_turns.iter().for_each(|&turn| current.apply_turn(turn));
+            turns.append(&mut stage_turns);
+        }
+
+        // Solves the Pyra Subgroup, whose Turns are the first Turns of the FTO
+        turns.extend(self.solver_pyra.solve(&StatePyra::from_fto(&current)?)?.turns());
+        Ok(Sequence::from_turns(&turns).simplified())
+    }
+}
@@ -1,46 +1,45 @@
-use crate::{pruning::{statesetfto2::StateSetFTO2, PruningTable}, puzzle::Puzzle, state::{statefto2::{StateFTO2, FTO2_SYMHASH_TABLE}, symhash::SymHash, State}};
+use std::sync::Arc;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{error::Error, pruning::{heuristic::PruningHeuristic, statesetfto2::StateSetFTO2}, puzzle::sequence::Sequence, tablefile::MappedTable, tables::{SymTables, Tables}, state::{statefto2::StateFTO2, symhash::SymHash}};
+
+use super::idastar::{Heuristic, IdaStar};
 
 
 pub struct SolverFTO2 {
-    pruning_table: Vec<u8>
+    heuristic: PruningHeuristic<StateFTO2, StateSetFTO2, MappedTable>
 }
 
 impl SolverFTO2 {
-    pub fn new() -> Self {
-        Self {
-            pruning_table: match PruningTable::<StateSetFTO2>::read_table_from_file() {
-                Ok(table) => table,
-                Err(_) => PruningTable::<StateSetFTO2>::new(true).generate(true)
-            }
-        }
+    pub fn new(tables: Arc<Tables>) -> Result<Self, Error> {
+        Ok(Self {
+            heuristic: PruningHeuristic::map(tables, 8, |tables: &Arc<SymTables>, fto2: &StateFTO2| fto2.get_sym_hash_indexed(tables.symindex()))?
+        })
+    }
+
+    // Fails for States, which can't be solved
+    pub fn solve(&self, fto2: &StateFTO2) -> Result<Sequence<StateFTO2>, Error> {
+        fto2.validate()?;
+        self.solutions(fto2).next().ok_or(Error::NoSolution)
+    }
+
+    // All Solutions ordered by length, take k for the k shortest
+    pub fn solutions(&self, fto2: &StateFTO2) -> impl Iterator<Item = Sequence<StateFTO2>> + '_ {
+        IdaStar::new(vec![&self.heuristic], 8).solutions(fto2).map(|turns| Sequence::from_turns(&turns))
+    }
+
+    pub fn optimal_solutions(&self, fto2: &StateFTO2) -> impl Iterator<Item = Sequence<StateFTO2>> + '_ {
+        let distance = self.distance(fto2) as usize;
+        self.solutions(fto2).take_while(move |solution| solution.len() == distance)
     }
 
-    pub fn solve(&self, fto2: &StateFTO2) -> String {
-        let mut solution = String::new();
-        let mut current = fto2.clone();
-        let mut depthm3 = self.get_depthm3(current.get_sym_hash(&FTO2_SYMHASH_TABLE));
-        let mut changed = true;
-        while changed {
-            changed = false;
-            for turn in 0..8 {
-                let mut potential = current.clone();
-                potential.apply_turn(turn);
-                let potential_depthm3 = self.get_depthm3(potential.get_sym_hash(&FTO2_SYMHASH_TABLE));
-                if (potential_depthm3 + 1) % 3 == depthm3 {
-                    solution += StateFTO2::get_notation(turn);
-                    solution.push(' ');
-                    current = potential;
-                    depthm3 = potential_depthm3;
-                    changed = true;
-                    break;
-                }
-            }
-        }
-        solution.pop();
-        solution
+    // Number of Turns needed to solve the State
+    pub fn distance(&self, fto2: &StateFTO2) -> u8 {
+        self.heuristic.get_bound(fto2)
     }
 
-    fn get_depthm3(&self, hash: usize) -> u8 {
-        PruningTable::<StateSetFTO2>::read(&self.pruning_table, hash)
+    pub fn distances(&self, fto2s: &[StateFTO2]) -> Vec<u8> {
+        fto2s.par_iter().map(|fto2| self.distance(fto2)).collect()
     }
 }
\ No newline at end of file
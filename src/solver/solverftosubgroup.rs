@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{error::Error, pruning::{heuristic::PruningHeuristic, stateset::StateSet, statesetftosubgroup::StateSetFTOSubgroup}, puzzle::sequence::Sequence, tables::Tables, state::{statefto::StateFTO, State}};
+
+use super::idastar::{Heuristic, IdaStar};
+
+
+// Phase 1: brings any FTO into the Domain of StatePyra::from_fto, the Edges of their own Trio on the four Positions it reads
+// Centers, Corners and the other Edges aren't reduced, so the FTO isn't in the Pyra Subgroup yet
+pub struct SolverFTOSubgroup {
+    heuristic: PruningHeuristic<StateFTO, StateSetFTOSubgroup>
+}
+
+impl SolverFTOSubgroup {
+    pub fn new(tables: Arc<Tables>) -> Result<Self, Error> {
+        Ok(Self {
+            heuristic: PruningHeuristic::load(tables, StateFTO::NUM_TURNS, |_, fto| StateSetFTOSubgroup::from_fto(fto).get_hash())?
+        })
+    }
+
+    // Fails for States, which can't be solved
+    pub fn solve(&self, fto: &StateFTO) -> Result<Sequence<StateFTO>, Error> {
+        fto.validate()?;
+        Ok(Sequence::from_turns(&IdaStar::new(vec![&self.heuristic], StateFTO::NUM_TURNS).solve(fto)?))
+    }
+
+    // Number of Turns needed to reach the Domain of StatePyra::from_fto
+    pub fn distance(&self, fto: &StateFTO) -> u8 {
+        self.heuristic.get_bound(fto)
+    }
+}
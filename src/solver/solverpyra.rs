@@ -1,46 +1,51 @@
-use crate::{pruning::{statesetpyra::StateSetPyra, PruningTable}, puzzle::Puzzle, state::{statefto::StateFTO, statepyra::{StatePyra, PYRA_SYMHASH_TABLE}, symhash::SymHash, State}};
+use std::sync::Arc;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{error::Error, pruning::{heuristic::PruningHeuristic, statesetpyra::StateSetPyra}, puzzle::sequence::Sequence, tablefile::MappedTable, tables::{SymTables, Tables}, state::{statepyra::StatePyra, symhash::SymHash}};
+
+use super::idastar::{Heuristic, IdaStar};
 
 
 pub struct SolverPyra {
-    pruning_table: Vec<u8>
+    heuristic: Box<dyn Heuristic<StatePyra> + Send + Sync>
 }
 
 impl SolverPyra {
-    pub fn new() -> Self {
+    pub fn new(tables: Arc<Tables>) -> Result<Self, Error> {
+        let heuristic: PruningHeuristic<StatePyra, StateSetPyra, MappedTable> = PruningHeuristic::map(tables, 8, |tables: &Arc<SymTables>, pyra: &StatePyra| pyra.get_sym_hash_indexed(tables.symindex()))?;
+        Ok(Self::with_heuristic(heuristic))
+    }
+
+    // Solves with another Heuristic, the Distances are exact only for an exact Heuristic
+    pub fn with_heuristic(heuristic: impl Heuristic<StatePyra> + Send + Sync + 'static) -> Self {
         Self {
-            pruning_table: match PruningTable::<StateSetPyra>::read_table_from_file() {
-                Ok(table) => table,
-                Err(_) => PruningTable::<StateSetPyra>::new(true).generate(true)
-            }
+            heuristic: Box::new(heuristic)
         }
     }
 
-    pub fn solve(&self, pyra: &StatePyra) -> String {
-        let mut solution = String::new();
-        let mut current = StateFTO::from_pyra(pyra);
-        let mut depthm3 = self.get_depthm3(StatePyra::from_fto(&current).get_sym_hash(&PYRA_SYMHASH_TABLE));
-        let mut changed = true;
-        while changed {
-            changed = false;
-            for turn in 0..8 {
-                let mut potential = current.clone();
-                potential.apply_turn(turn);
-                let potential_depthm3 = self.get_depthm3(StatePyra::from_fto(&potential).get_sym_hash(&PYRA_SYMHASH_TABLE));
-                if (potential_depthm3 + 1) % 3 == depthm3 {
-                    solution += StateFTO::get_notation(turn);
-                    solution.push(' ');
-                    current = potential;
-                    depthm3 = potential_depthm3;
-                    changed = true;
-                    break;
-                }
-            }
-        }
-        solution.pop();
-        solution
+    // Fails for States, which can't be solved
+    pub fn solve(&self, pyra: &StatePyra) -> Result<Sequence<StatePyra>, Error> {
+        pyra.validate()?;
+        self.solutions(pyra).next().ok_or(Error::NoSolution)
+    }
+
+    // All Solutions ordered by length, take k for the k shortest
+    pub fn solutions(&self, pyra: &StatePyra) -> impl Iterator<Item = Sequence<StatePyra>> + '_ {
+        IdaStar::new(vec![&*self.heuristic], 8).solutions(pyra).map(|turns| Sequence::from_turns(&turns))
+    }
+
+    pub fn optimal_solutions(&self, pyra: &StatePyra) -> impl Iterator<Item = Sequence<StatePyra>> + '_ {
+        let distance = self.distance(pyra) as usize;
+        self.solutions(pyra).take_while(move |solution| solution.len() == distance)
+    }
+
+    // Number of Turns needed to solve the State
+    pub fn distance(&self, pyra: &StatePyra) -> u8 {
+        self.heuristic.get_bound(pyra)
     }
 
-    fn get_depthm3(&self, hash: usize) -> u8 {
-        PruningTable::<StateSetPyra>::read(&self.pruning_table, hash)
+    pub fn distances(&self, pyras: &[StatePyra]) -> Vec<u8> {
+        pyras.par_iter().map(|pyra| self.distance(pyra)).collect()
     }
 }
\ No newline at end of file
@@ -6,14 +6,19 @@ pub mod symhash;
 
 pub mod statefto;
 pub mod statepyra;
+pub mod stateftocorners;
+pub mod stateftoedges;
+pub mod stateftoedgetrio;
+pub mod stateftocenters;
+
+use std::fmt;
+
+use crate::error::Error;
 
 pub trait State: Sized + Clone {
-    const RAW_SIZE: usize;
     const NUM_TURNS: u8;
     const NUM_SYMS: u8;
     fn solved() -> Self;
-    fn from_hash(hash: usize) -> Self;
-    fn get_hash(&self) -> usize;
     fn apply_turn(&mut self, turn: u8);
     fn wrap_sym(&mut self, sym: u8);
 
@@ -34,6 +39,23 @@ pub trait State: Sized + Clone {
     }
 }
 
+// States, which are numbered by a Hash from 0 to RAW_SIZE
+// The full FTO is too large to be hashed, its Coordinates are
+pub trait RawHash: State {
+    const RAW_SIZE: usize;
+    fn from_hash(hash: usize) -> Self;
+    fn get_hash(&self) -> usize;
+
+    // Checks the Hash instead of panicking, for Hashes from outside
+    fn try_from_hash(hash: usize) -> Result<Self, Error> {
+        if hash < Self::RAW_SIZE {
+            Ok(Self::from_hash(hash))
+        } else {
+            Err(Error::HashOutOfRange(hash, Self::RAW_SIZE))
+        }
+    }
+}
+
 pub fn hash_permutation(arr: &[u8]) -> usize {
     let mut acc: usize = 0;
     let mut factor: usize = 1;
@@ -49,6 +71,28 @@ pub fn hash_permutation(arr: &[u8]) -> usize {
     acc
 }
 
+// Even Permutation of N Pieces from its Hash, the Parity decides the first two Pieces
+pub fn unhash_permutation<const N: usize>(hash: usize) -> [u8; N] {
+    let mut arr = [N as u8; N];
+    let mut left = hash;
+    let mut pieces: Vec<u8> = (0..N as u8).rev().collect();
+    let mut factor = (3..N).product::<usize>();
+    let mut parity = 0;
+    for i in 0..N - 2 {
+        let p = left / factor;
+        left %= factor;
+        factor /= N - 1 - i;
+        parity += p;
+        arr[N - 1 - i] = pieces.remove(p);
+    }
+    (arr[0], arr[1]) = if parity & 1 == 0 {
+        (pieces[1], pieces[0])
+    } else {
+        (pieces[0], pieces[1])
+    };
+    arr
+}
+
 pub fn hash_orientation(arr: &[bool]) -> usize {
     arr[1..].iter().fold(0, |acc, &oriented| if oriented {acc * 2} else {acc * 2 + 1})
 }
@@ -57,9 +101,74 @@ pub fn hash_centers(arr: &[u8]) -> usize {
     hash_centers_any(arr, 3, 4)
 }
 
+pub fn unhash_centers(hash: usize) -> [u8; 12] {
+    let mut arr = [3; 12];
+    let mut left = hash;
+    for color in 0..3 {
+        let factor = (color + 1..3).fold(1, |acc, other| acc * biko(12 - other * 3, 3));
+        let mut color_hash = left / factor;
+        left %= factor;
+        let mut slots = 12 - color * 3;
+        let mut same = 3;
+        for center in arr.iter_mut() {
+            if *center != 3 {
+                continue;
+            }
+            slots -= 1;
+            if same == 0 {
+                break;
+            }
+            let with_color = biko(slots, same - 1);
+            if color_hash < with_color {
+                *center = color as u8;
+                same -= 1;
+            } else {
+                color_hash -= with_color;
+            }
+        }
+    }
+    arr
+}
+
+pub fn hash_combination(arr: &[bool]) -> usize {
+    let mut left = arr.iter().filter(|&&set| set).count();
+    let mut hash = 0;
+    for (index, &set) in arr.iter().enumerate() {
+        if left == 0 {
+            break;
+        }
+        if set {
+            left -= 1;
+        } else {
+            hash += biko(arr.len() - index - 1, left - 1);
+        }
+    }
+    hash
+}
+
+pub fn unhash_combination<const N: usize>(hash: usize, set: usize) -> [bool; N] {
+    let mut arr = [false; N];
+    let mut left = set;
+    let mut hash = hash;
+    for (index, entry) in arr.iter_mut().enumerate() {
+        if left == 0 {
+            break;
+        }
+        let with_set = biko(N - index - 1, left - 1);
+        if hash < with_set {
+            *entry = true;
+            left -= 1;
+        } else {
+            hash -= with_set;
+        }
+    }
+    arr
+}
+
 fn hash_centers_any(arr: &[u8], same: usize, colors: usize) -> usize {
-    let mut hash: Vec<usize> = vec![0; colors-1];
-    let mut left: Vec<usize> = vec![same; colors];
+    let mut hash = [0; 4];
+    let mut left = [0; 4];
+    left[..colors].fill(same);
     for &piece in arr {
         left[piece as usize] -= 1;
         for color in 0..piece {
@@ -68,7 +177,7 @@ fn hash_centers_any(arr: &[u8], same: usize, colors: usize) -> usize {
             }
         }
     }
-    hash.iter().enumerate().fold(0, |acc, (index, &val)| acc * biko((colors - index) * same, same) + val)
+    hash[..colors - 1].iter().enumerate().fold(0, |acc, (index, &val)| acc * biko((colors - index) * same, same) + val)
 }
 
 fn hash_center_turn(arr: &[u8]) -> usize {
@@ -76,12 +185,24 @@ fn hash_center_turn(arr: &[u8]) -> usize {
 }
 
 fn biko(n: usize, k: usize) -> usize {
-    product(n - k, n) / product(1, k)
+    BIKO[n][k]
 }
 
-fn product(start: usize, end: usize) -> usize {
-    (start+1..end+1).fold(1, |acc, n| acc * n)
-}
+// Binomial Coefficients up to 12
+const BIKO: [[usize; 13]; 13] = {
+    let mut biko = [[0; 13]; 13];
+    let mut n = 0;
+    while n < 13 {
+        biko[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            biko[n][k] = biko[n - 1][k - 1] + biko[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    biko
+};
 
 pub fn unwrap_sym(sym: u8) -> (bool, bool, bool, u8) {
     (
@@ -120,6 +241,91 @@ pub fn unwrap_turn(turn: u8) -> (u8, bool) {
     )
 }
 
+// Reasons, why a State can't be reached from the solved State
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    MissingCorner(u8),
+    MissingEdge(u8),
+    MissingCenter(u8),
+    OddCornerPermutation,
+    OddEdgePermutation,
+    OddCenterPermutation,
+    CornerTwisted(u8),
+    CenterCount(u8, usize),
+    CenterTwist(u8)
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCorner(corner) => write!(f, "corner {corner} missing"),
+            Self::MissingEdge(edge) => write!(f, "edge {edge} missing"),
+            Self::MissingCenter(center) => write!(f, "center {center} missing"),
+            Self::OddCornerPermutation => write!(f, "odd corner permutation"),
+            Self::OddEdgePermutation => write!(f, "odd edge permutation"),
+            Self::OddCenterPermutation => write!(f, "odd center permutation"),
+            Self::CornerTwisted(corner) => write!(f, "corner {corner} twisted"),
+            Self::CenterCount(color, count) => write!(f, "{count} centers of color {color}, expected 3"),
+            Self::CenterTwist(center) => write!(f, "center {center} has an invalid twist")
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+// Checks, that all Pieces are there once and that their Permutation is even
+fn validate_permutation(arr: &[u8], missing: fn(u8) -> StateError, odd: StateError) -> Result<(), StateError> {
+    if let Some(piece) = (0..arr.len() as u8).find(|piece| !arr.contains(piece)) {
+        return Err(missing(piece));
+    }
+    let inversions = (0..arr.len()).map(|index| arr[index + 1..].iter().filter(|&&other| other < arr[index]).count()).sum::<usize>();
+    if inversions & 1 == 1 {
+        return Err(odd);
+    }
+    Ok(())
+}
+
+// Checks, that an even Number of Corners is flipped
+fn validate_orientation(co: &[bool]) -> Result<(), StateError> {
+    if co.iter().filter(|&&oriented| !oriented).count() & 1 == 1 {
+        let corner = co.iter().position(|&oriented| !oriented).unwrap();
+        return Err(StateError::CornerTwisted(corner as u8));
+    }
+    Ok(())
+}
+
+// Checks, that each of the four Colors is on three Centers
+fn validate_center_colors(ce: &[u8], first_color: u8) -> Result<(), StateError> {
+    for color in 0..4 {
+        let count = ce.iter().filter(|&&center| center == color).count();
+        if count != 3 {
+            return Err(StateError::CenterCount(color + first_color, count));
+        }
+    }
+    Ok(())
+}
+
+// Gets the Color of a Corner Sticker, Colors 0 to 3 are the Centers of ce1 and 4 to 7 of ce2
+pub fn get_corner_color(corner: u8, oriented: bool, number: u8) -> u8 {
+    match CORNER_COLORS.get(corner as usize) {
+        Some(colors) => {
+            if number & 1 == 0 {
+                if (number == 0) == oriented {colors[0]} else {colors[1]}
+            } else {
+                if (number == 1) == oriented {colors[2]} else {colors[3]}
+            }
+        }
+        None => 8
+    }
+}
+
+pub fn get_edge_color(edge: u8, u_orbit: bool) -> u8 {
+    match EDGE_COLORS.get(edge as usize) {
+        Some(colors) => if u_orbit {colors[0]} else {colors[1]},
+        None => 8
+    }
+}
+
 // IDs
 
 const ID_CP: [u8; 6] = [0, 1, 2, 3, 4, 5];
@@ -128,6 +334,11 @@ const ID_CE: [u8; 4] = [0, 1, 2, 3];
 const ID_EC: [u8; 12] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
 const ID_CT: [u8; 4] = [0; 4];
 
+// Sticker Colors
+
+const CORNER_COLORS: [[u8; 4]; 6] = [[0, 1, 7, 6], [0, 2, 6, 5], [0, 3, 5, 7], [1, 3, 7, 4], [2, 1, 6, 4], [3, 2, 5, 4]];
+const EDGE_COLORS: [[u8; 2]; 12] = [[0, 6], [0, 5], [0, 7], [1, 7], [1, 4], [1, 6], [2, 6], [2, 4], [2, 5], [3, 5], [3, 4], [3, 7]];
+
 // FTO2 Turns
 
 const TURN_D_CP: [u8; 6] = [0, 1, 2, 5, 3, 4];
@@ -246,4 +457,44 @@ const SYM_ZX2_CT: [u8; 12] = [6, 7, 8, 9, 10, 11, 0, 1, 2, 3, 4, 5];
 const SYM_M_CT: [u8; 12] = SYM_M_CE1;
 
 const MAP_CP_U: [u8; 6] = [0, 1, 2, 4, 7, 10];
-const MAP_CP_F: [u8; 6] = [3, 6, 9, 11, 5, 8];
\ No newline at end of file
+const MAP_CP_F: [u8; 6] = [3, 6, 9, 11, 5, 8];
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{statefto::StateFTO, stateftoedges::StateFTOEdges, *};
+
+    fn assert_round_trip<const N: usize>(hash: usize) {
+        let arr = unhash_permutation::<N>(hash);
+        let mut sorted = arr;
+        sorted.sort();
+        assert!(sorted.iter().copied().eq(0..N as u8), "{arr:?} is no Permutation");
+        assert_eq!(hash_permutation(&arr), hash);
+    }
+
+    #[test]
+    fn permutations_of_six_round_trip() {
+        for hash in 0..360 {
+            assert_round_trip::<6>(hash);
+        }
+    }
+
+    #[test]
+    fn permutations_of_twelve_round_trip() {
+        for hash in (0..StateFTOEdges::RAW_SIZE).step_by(9_973).chain([StateFTOEdges::RAW_SIZE - 1]) {
+            assert_round_trip::<12>(hash);
+        }
+    }
+
+    #[test]
+    fn turned_edges_round_trip() {
+        let mut rng = StdRng::seed_from_u64(22);
+        let mut fto = StateFTO::solved();
+        for _ in 0..1000 {
+            fto.apply_turn(rng.random_range(0..StateFTO::NUM_TURNS));
+            let hash = hash_permutation(&fto.ep);
+            assert!(hash < StateFTOEdges::RAW_SIZE);
+            assert_eq!(unhash_permutation::<12>(hash), fto.ep);
+        }
+    }
+}
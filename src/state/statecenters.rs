@@ -1,20 +1,14 @@
-use std::sync::LazyLock;
-
-use crate::moving::{sym::SymTable, turn::TurnTable};
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}};
 
 use super::*;
 
 
-pub static CENTERS_TURN_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateCenters::get_turn_table());
-pub static CENTERS_SYM_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateCenters::get_sym_table());
-
 #[derive(Clone, Debug)]
 pub struct StateCenters {
     ce: [u8; 4]
 }
 
 impl State for StateCenters {
-    const RAW_SIZE: usize = 12;
     const NUM_TURNS: u8 = 8;
     const NUM_SYMS: u8 = 24;
 
@@ -24,33 +18,6 @@ impl State for StateCenters {
         }
     }
 
-    fn from_hash(hash: usize) -> Self {
-        let mut ce = [4; 4];
-        let mut left = hash;
-        let mut pieces = vec![3, 2, 1, 0];
-        let mut factor = 3;
-        let mut parity = 0;
-        for i in 0..2 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 3 - i;
-            parity += p;
-            ce[3 - i] = pieces.remove(p);
-        }
-        (ce[0], ce[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-        Self {
-            ce
-        }
-    }
-
-    fn get_hash(&self) -> usize {
-        hash_permutation(&self.ce)
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (face, cw) = unwrap_turn(turn);
         match face {
@@ -105,16 +72,49 @@ impl State for StateCenters {
     }
 }
 
+impl RawHash for StateCenters {
+    const RAW_SIZE: usize = 12;
+
+    fn from_hash(hash: usize) -> Self {
+        let mut ce = [4; 4];
+        let mut left = hash;
+        let mut pieces = vec![3, 2, 1, 0];
+        let mut factor = 3;
+        let mut parity = 0;
+        for i in 0..2 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 3 - i;
+            parity += p;
+            ce[3 - i] = pieces.remove(p);
+        }
+        (ce[0], ce[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+        Self {
+            ce
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_permutation(&self.ce)
+    }
+}
+
 impl TurnTable for StateCenters {
     const TURNTABLE_NAME: &str = "centers";
     const NUM_HASHES: usize = Self::RAW_SIZE;
 
-    fn from_turntable_index(hash: usize) -> Self {
+    type Index = ();
+
+    fn from_turntable_index(_index: &(), hash: usize) -> Self {
         Self::from_hash(hash)
     }
 
-    fn get_turntable_entry(&self) -> usize {
-        self.get_hash()
+    fn get_turntable_entry(&self, _index: &()) -> Result<usize, Error> {
+        Ok(self.get_hash())
     }
 }
 
@@ -1,20 +1,15 @@
-use std::sync::LazyLock;
-
-use crate::moving::turn::TurnTable;
+use crate::{error::Error, moving::turn::TurnTable, tables::SymTables};
 
 use super::{symhash::SymHash, *};
 
-pub static CORNERS_SYMHASH_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateCorners::get_symhash_table());
-pub static CORNERS_TURN_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateCorners::get_turn_table());
 
 #[derive(Clone, Debug)]
 pub struct StateCorners {
-    cp: [u8; 6],
-    co: [bool; 6]
+    pub(crate) cp: [u8; 6],
+    pub(crate) co: [bool; 6]
 }
 
 impl State for StateCorners {
-    const RAW_SIZE: usize = 11_520;
     const NUM_TURNS: u8 = 8;
     const NUM_SYMS: u8 = 24;
 
@@ -25,45 +20,6 @@ impl State for StateCorners {
         }
     }
 
-    fn from_hash(hash: usize) -> Self {
-        let mut cp = [6; 6];
-        let mut left = hash >> 5;
-        let mut pieces = vec![5, 4, 3, 2, 1, 0];
-        let mut factor = 60;
-        let mut parity = 0;
-        for i in 0..4 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 5 - i;
-            parity += p;
-            cp[5 - i] = pieces.remove(p);
-        }
-        (cp[0], cp[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-
-        let mut co = [true; 6];
-        left = hash & 31;
-        let mut even = true;
-        for i in 0..5 {
-            let o = left & 1 == 0;
-            left /= 2;
-            co[5 - i] = o;
-            even = even == o;
-        }
-        co[0] = even;
-        Self {
-            cp,
-            co
-        }
-    }
-
-    fn get_hash(&self) -> usize {
-        hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (face, cw) = unwrap_turn(turn);
         match face {
@@ -118,6 +74,49 @@ impl State for StateCorners {
     }
 }
 
+impl RawHash for StateCorners {
+    const RAW_SIZE: usize = 11_520;
+
+    fn from_hash(hash: usize) -> Self {
+        let mut cp = [6; 6];
+        let mut left = hash >> 5;
+        let mut pieces = vec![5, 4, 3, 2, 1, 0];
+        let mut factor = 60;
+        let mut parity = 0;
+        for i in 0..4 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 5 - i;
+            parity += p;
+            cp[5 - i] = pieces.remove(p);
+        }
+        (cp[0], cp[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+
+        let mut co = [true; 6];
+        left = hash & 31;
+        let mut even = true;
+        for i in 0..5 {
+            let o = left & 1 == 0;
+            left /= 2;
+            co[5 - i] = o;
+            even = even == o;
+        }
+        co[0] = even;
+        Self {
+            cp,
+            co
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)
+    }
+}
+
 impl SymHash for StateCorners {
     const SYM_NAME: &str = "corners";
     const SYM_SIZE: usize = 504;
@@ -127,13 +126,15 @@ impl TurnTable for StateCorners {
     const TURNTABLE_NAME: &str = "corners";
     const NUM_HASHES: usize = Self::SYM_SIZE;
 
-    fn from_turntable_index(hash: usize) -> Self {
-        Self::from_sym_hash(&CORNERS_SYMHASH_TABLE, hash)
+    type Index = SymTables;
+
+    fn from_turntable_index(index: &SymTables, hash: usize) -> Self {
+        Self::from_sym_hash(index.symhash(), hash)
     }
 
-    fn get_turntable_entry(&self) -> usize {
-        let (sym_hash, sym) = self.get_sym_hash_sym(&CORNERS_SYMHASH_TABLE);
-        sym_hash * Self::NUM_SYMS as usize + sym as usize
+    fn get_turntable_entry(&self, index: &SymTables) -> Result<usize, Error> {
+        let (sym_hash, sym) = self.get_sym_hash_sym_indexed(index.symindex());
+        Ok(sym_hash * Self::NUM_SYMS as usize + sym as usize)
     }
 }
 
@@ -6,7 +6,6 @@ pub struct StateCP {
 }
 
 impl State for StateCP {
-    const RAW_SIZE: usize = 360;
     const NUM_TURNS: u8 = 8;
     const NUM_SYMS: u8 = 24;
 
@@ -16,33 +15,6 @@ impl State for StateCP {
         }
     }
 
-    fn from_hash(hash: usize) -> Self {
-        let mut cp = [6; 6];
-        let mut left = hash;
-        let mut pieces = vec![5, 4, 3, 2, 1, 0];
-        let mut factor = 60;
-        let mut parity = 0;
-        for i in 0..4 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 5 - i;
-            parity += p;
-            cp[5 - i] = pieces.remove(p);
-        }
-        (cp[0], cp[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-        Self {
-            cp
-        }
-    }
-
-    fn get_hash(&self) -> usize {
-        hash_permutation(&self.cp)
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (face, cw) = unwrap_turn(turn);
         match face {
@@ -97,6 +69,37 @@ impl State for StateCP {
     }
 }
 
+impl RawHash for StateCP {
+    const RAW_SIZE: usize = 360;
+
+    fn from_hash(hash: usize) -> Self {
+        let mut cp = [6; 6];
+        let mut left = hash;
+        let mut pieces = vec![5, 4, 3, 2, 1, 0];
+        let mut factor = 60;
+        let mut parity = 0;
+        for i in 0..4 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 5 - i;
+            parity += p;
+            cp[5 - i] = pieces.remove(p);
+        }
+        (cp[0], cp[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+        Self {
+            cp
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_permutation(&self.cp)
+    }
+}
+
 impl SymHash for StateCP {
     const SYM_NAME: &str = "cp";
     const SYM_SIZE: usize = 22;
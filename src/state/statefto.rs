@@ -1,5 +1,12 @@
-use super::{statepyra::StatePyra, *};
+use std::fmt;
 
+use rand::Rng;
+
+
+use super::{statecorners::StateCorners, stateftocenters::StateFTOCenters, stateftoedges::StateFTOEdges, statepyra::StatePyra, *};
+
+// Permutations and Orientation of a Face Turn: cp, co, ep, ce1, ce2
+type FaceTurn = ([u8; 6], [bool; 6], [u8; 12], [u8; 12], [u8; 12]);
 
 #[derive(Debug, Clone)]
 pub struct StateFTO {
@@ -11,7 +18,6 @@ pub struct StateFTO {
 }
 
 impl State for StateFTO {
-    const RAW_SIZE: usize = 1; // actual size: 11_520 * 369_600 * 369_600 * 239_500_800
     const NUM_TURNS: u8 = 16;
     const NUM_SYMS: u8 = 48;
 
@@ -25,14 +31,6 @@ impl State for StateFTO {
         }
     }
 
-    fn from_hash(_hash: usize) -> Self {
-        panic!("Can't hash entire FTO")
-    }
-
-    fn get_hash(&self) -> usize {
-        panic!("Can't hash entire FTO")
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (face, cw) = unwrap_turn(turn);
         match face {
@@ -148,6 +146,115 @@ impl StateFTO {
         fto
     }
 
+    pub fn scrambled() -> Self {
+        Self::scrambled_with(&mut rand::rng())
+    }
+
+    // Uniform random State drawn from the given Generator, for reproducible Scrambles
+    // The Parts are independent: Corners and Edges are even Permutations on their own,
+    // and the Parity of the Centers can't be seen, since three Centers share a Color
+    pub fn scrambled_with<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let corners = StateCorners::from_hash(rng.random_range(0..StateCorners::RAW_SIZE));
+        Self {
+            cp: corners.cp,
+            co: corners.co,
+            ep: unhash_permutation(rng.random_range(0..StateFTOEdges::RAW_SIZE)),
+            ce1: unhash_centers(rng.random_range(0..StateFTOCenters::<1>::RAW_SIZE)),
+            ce2: unhash_centers(rng.random_range(0..StateFTOCenters::<2>::RAW_SIZE))
+        }
+    }
+
+    // Checks, that the State can be solved, Center Colors are numbered like the Facelets
+    pub fn validate(&self) -> Result<(), StateError> {
+        validate_permutation(&self.cp, StateError::MissingCorner, StateError::OddCornerPermutation)?;
+        validate_orientation(&self.co)?;
+        validate_permutation(&self.ep, StateError::MissingEdge, StateError::OddEdgePermutation)?;
+        validate_center_colors(&self.ce1, 0)?;
+        validate_center_colors(&self.ce2, 4)
+    }
+
+    // 72 Stickers as Color Letters, for each Face in the order D, B, L, R, U, F, BL, BR
+    // from the top Row of the printed Net to the bottom one
+    pub fn to_facelets(&self) -> String {
+        FACELETS.iter().map(|facelet| COLOR_LETTERS[self.get_facelet_color(facelet) as usize]).collect()
+    }
+
+    // Reads 72 Color Letters, ignoring whitespace
+    pub fn from_facelets(facelets: &str) -> Result<Self, FaceletError> {
+        let letters: Vec<char> = facelets.chars().filter(|letter| !letter.is_whitespace()).collect();
+        if letters.len() != FACELETS.len() {
+            return Err(FaceletError::Length(letters.len()));
+        }
+        let mut colors = [0; 72];
+        for (position, &letter) in letters.iter().enumerate() {
+            colors[position] = match COLOR_LETTERS.iter().position(|&other| other == letter.to_ascii_uppercase()) {
+                Some(color) => color as u8,
+                None => return Err(FaceletError::Letter(position, letter))
+            };
+        }
+        // Collects the Colors of every Sticker of each Piece
+        let mut corners = [[8; 4]; 6];
+        let mut edges = [[8; 2]; 12];
+        let mut fto = Self::solved();
+        for (position, facelet) in FACELETS.iter().enumerate() {
+            let color = colors[position];
+            match *facelet {
+                Facelet::Corner(corner, number) => corners[corner][number as usize] = color,
+                Facelet::Edge(edge, u_orbit) => edges[edge][if u_orbit {0} else {1}] = color,
+                Facelet::Ce1(center) if color < 4 => fto.ce1[center] = color,
+                Facelet::Ce2(center) if color >= 4 => fto.ce2[center] = color - 4,
+                _ => return Err(FaceletError::Center(position))
+            }
+        }
+        for (corner, colors) in corners.iter().enumerate() {
+            (fto.cp[corner], fto.co[corner]) = (0..6).flat_map(|piece| [(piece, true), (piece, false)]).find(|&(piece, oriented)| {
+                (0..4).all(|number| get_corner_color(piece, oriented, number) == colors[number as usize])
+            }).ok_or(FaceletError::Corner(corner))?;
+        }
+        for (edge, colors) in edges.iter().enumerate() {
+            fto.ep[edge] = (0..12).find(|&piece| {
+                get_edge_color(piece, true) == colors[0] && get_edge_color(piece, false) == colors[1]
+            }).ok_or(FaceletError::Edge(edge))?;
+        }
+        Ok(fto)
+    }
+
+    // Letters of the Colors in the Facelets, in either Case
+    pub fn is_color_letter(letter: char) -> bool {
+        COLOR_LETTERS.contains(&letter.to_ascii_uppercase())
+    }
+
+    fn get_facelet_color(&self, facelet: &Facelet) -> u8 {
+        match *facelet {
+            Facelet::Corner(corner, number) => get_corner_color(self.cp[corner], self.co[corner], number),
+            Facelet::Edge(edge, u_orbit) => get_edge_color(self.ep[edge], u_orbit),
+            Facelet::Ce1(center) => self.ce1[center],
+            Facelet::Ce2(center) => self.ce2[center] + 4
+        }
+    }
+
+    // Turns of two Faces commute, if they move disjoint Pieces
+    pub fn faces_commute(face: u8, other: u8) -> bool {
+        let (cp, co, ep, ce1, ce2) = Self::get_face_turn(face);
+        let (other_cp, other_co, other_ep, other_ce1, other_ce2) = Self::get_face_turn(other);
+        let disjoint = |perm: &[u8], other_perm: &[u8]| (0..perm.len()).all(|i| perm[i] as usize == i || other_perm[i] as usize == i);
+        disjoint(&cp, &other_cp) && disjoint(&ep, &other_ep) && disjoint(&ce1, &other_ce1) && disjoint(&ce2, &other_ce2)
+            && (0..6).all(|i| co[i] || other_co[i])
+    }
+
+    fn get_face_turn(face: u8) -> FaceTurn {
+        match face {
+            0 => (TURN_D_CP, TURN_D_CO, TURN_D_EP, TURN_D_CE1, TURN_D_CE2),
+            1 => (TURN_B_CP, TURN_B_CO, TURN_B_EP, TURN_B_CE1, TURN_B_CE2),
+            2 => (TURN_L_CP, TURN_L_CO, TURN_L_EP, TURN_L_CE1, TURN_L_CE2),
+            3 => (TURN_R_CP, TURN_R_CO, TURN_R_EP, TURN_R_CE1, TURN_R_CE2),
+            4 => (TURN_U_CP, TURN_U_CO, TURN_U_EP, TURN_U_CE1, TURN_U_CE2),
+            5 => (TURN_F_CP, TURN_F_CO, TURN_F_EP, TURN_F_CE1, TURN_F_CE2),
+            6 => (TURN_BL_CP, TURN_BL_CO, TURN_BL_EP, TURN_BL_CE1, TURN_BL_CE2),
+            _ => (TURN_BR_CP, TURN_BR_CO, TURN_BR_EP, TURN_BR_CE1, TURN_BR_CE2)
+        }
+    }
+
     pub fn apply(&mut self, cp: [u8; 6], co: [bool; 6], ep: [u8; 12], ce1: [u8; 12], ce2: [u8; 12]) {
         (self.cp, self.co) = {
             let mut new_cp = [6; 6];
@@ -214,7 +321,7 @@ impl StateFTO {
         (self.ce1, self.ce2) = (self.ce2, self.ce1);
     }
 
-    fn apply_permutation_ce1(&mut self, cp: [u8; 6], co: [bool; 6]) {
+    pub(crate) fn apply_permutation_ce1(&mut self, cp: [u8; 6], co: [bool; 6]) {
         self.ce1 = {
             let mut new_ce1 = [12; 12];
             for i in 0..6 {
@@ -230,4 +337,251 @@ impl StateFTO {
         };
         
     }
-}
\ No newline at end of file
+}
+
+// Color Letters of White, Green, Orange, grAy, Yellow, Blue, Purple and Red
+const COLOR_LETTERS: [char; 8] = ['W', 'G', 'O', 'A', 'Y', 'B', 'P', 'R'];
+
+// Piece Positions, the Corners and Edges with the Number of their Sticker
+enum Facelet {
+    Corner(usize, u8),
+    Edge(usize, bool),
+    Ce1(usize),
+    Ce2(usize)
+}
+
+const FACELETS: [Facelet; 72] = {
+    use Facelet::*;
+    [
+        // D
+        Corner(3, 3), Edge(4, false), Ce2(2), Edge(10, false), Corner(4, 3), Ce2(1), Edge(7, false), Ce2(0), Corner(5, 3),
+        // B
+        Corner(5, 1), Ce2(3), Edge(9, false), Ce2(4), Corner(2, 1), Edge(8, false), Ce2(5), Edge(1, false), Corner(1, 3),
+        // L
+        Corner(1, 1), Ce2(7), Edge(0, false), Ce2(8), Corner(0, 3), Edge(6, false), Ce2(6), Edge(5, false), Corner(4, 1),
+        // R
+        Corner(2, 3), Edge(2, false), Ce2(11), Edge(11, false), Corner(0, 1), Ce2(10), Edge(3, false), Ce2(9), Corner(3, 1),
+        // U
+        Corner(2, 0), Edge(1, true), Ce1(2), Edge(2, true), Corner(1, 0), Ce1(1), Edge(0, true), Ce1(0), Corner(0, 0),
+        // F
+        Corner(0, 2), Ce1(3), Edge(3, true), Ce1(4), Corner(3, 0), Edge(5, true), Ce1(5), Edge(4, true), Corner(4, 2),
+        // BL
+        Corner(1, 2), Edge(8, true), Ce1(6), Edge(6, true), Corner(5, 2), Ce1(8), Edge(7, true), Ce1(7), Corner(4, 0),
+        // BR
+        Corner(2, 2), Ce1(9), Edge(9, true), Ce1(10), Corner(5, 0), Edge(11, true), Ce1(11), Edge(10, true), Corner(3, 2)
+    ]
+};
+
+// Positions count the Stickers without whitespace, the Pieces are the Positions in StateFTO
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FaceletError {
+    Length(usize),
+    Letter(usize, char),
+    Center(usize),
+    Corner(usize),
+    Edge(usize)
+}
+
+impl fmt::Display for FaceletError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(length) => write!(f, "Expected 72 stickers, got {length}"),
+            Self::Letter(position, letter) => write!(f, "Unknown color '{letter}' at sticker {position}"),
+            Self::Center(position) => write!(f, "Center at sticker {position} has a color of the wrong orbit"),
+            Self::Corner(corner) => write!(f, "Corner {corner} has no valid colors"),
+            Self::Edge(edge) => write!(f, "Edge {edge} has no valid colors")
+        }
+    }
+}
+
+impl std::error::Error for FaceletError {}
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn assert_same(fto: &StateFTO, other: &StateFTO) {
+        assert_eq!((fto.cp, fto.co, fto.ep, fto.ce1, fto.ce2), (other.cp, other.co, other.ep, other.ce1, other.ce2));
+    }
+
+    // Solved Facelets with the Sticker at the Position replaced
+    fn facelets_with(position: usize, letter: char) -> String {
+        let mut letters: Vec<char> = StateFTO::solved().to_facelets().chars().collect();
+        letters[position] = letter;
+        letters.into_iter().collect()
+    }
+
+    fn position_of(facelet: impl Fn(&Facelet) -> bool) -> usize {
+        FACELETS.iter().position(facelet).unwrap()
+    }
+
+    #[test]
+    fn facelets_round_trip() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            let fto = StateFTO::scrambled_with(&mut rng);
+            let facelets = fto.to_facelets();
+            assert_eq!(facelets.len(), 72);
+            assert_same(&StateFTO::from_facelets(&facelets).unwrap(), &fto);
+        }
+    }
+
+    #[test]
+    fn facelets_ignore_whitespace_and_case() {
+        let facelets = StateFTO::solved().to_facelets();
+        let spaced: String = facelets.as_bytes().chunks(9).map(|face| String::from_utf8_lossy(face).to_lowercase()).collect::<Vec<String>>().join("\n ");
+        assert_same(&StateFTO::from_facelets(&spaced).unwrap(), &StateFTO::solved());
+    }
+
+    #[test]
+    fn wrong_length_is_rejected() {
+        let facelets = StateFTO::solved().to_facelets();
+        assert_eq!(StateFTO::from_facelets(&facelets[1..]).unwrap_err(), FaceletError::Length(71));
+        assert_eq!(StateFTO::from_facelets(&format!("{facelets}W")).unwrap_err(), FaceletError::Length(73));
+    }
+
+    #[test]
+    fn unknown_letter_is_rejected() {
+        assert_eq!(StateFTO::from_facelets(&facelets_with(5, 'X')).unwrap_err(), FaceletError::Letter(5, 'X'));
+    }
+
+    #[test]
+    fn center_of_the_other_orbit_is_rejected() {
+        let position = position_of(|facelet| matches!(facelet, Facelet::Ce1(_)));
+        assert_eq!(StateFTO::from_facelets(&facelets_with(position, 'Y')).unwrap_err(), FaceletError::Center(position));
+        let position = position_of(|facelet| matches!(facelet, Facelet::Ce2(_)));
+        assert_eq!(StateFTO::from_facelets(&facelets_with(position, 'W')).unwrap_err(), FaceletError::Center(position));
+    }
+
+    // Two Stickers of a Piece with the same Color
+    #[test]
+    fn invalid_corner_is_rejected() {
+        let facelets = StateFTO::solved().to_facelets();
+        let position = position_of(|facelet| matches!(facelet, Facelet::Corner(2, 0)));
+        let other = position_of(|facelet| matches!(facelet, Facelet::Corner(2, 1)));
+        let letter = facelets.chars().nth(other).unwrap();
+        assert_eq!(StateFTO::from_facelets(&facelets_with(position, letter)).unwrap_err(), FaceletError::Corner(2));
+    }
+
+    #[test]
+    fn invalid_edge_is_rejected() {
+        let facelets = StateFTO::solved().to_facelets();
+        let position = position_of(|facelet| matches!(facelet, Facelet::Edge(7, true)));
+        let other = position_of(|facelet| matches!(facelet, Facelet::Edge(7, false)));
+        let letter = facelets.chars().nth(other).unwrap();
+        assert_eq!(StateFTO::from_facelets(&facelets_with(position, letter)).unwrap_err(), FaceletError::Edge(7));
+    }
+
+    #[test]
+    fn turned_states_are_valid() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut fto = StateFTO::solved();
+        for _ in 0..1000 {
+            fto.apply_turn(rng.random_range(0..StateFTO::NUM_TURNS));
+            assert_eq!(fto.validate(), Ok(()));
+        }
+    }
+
+    fn validated(change: impl FnOnce(&mut StateFTO)) -> Result<(), StateError> {
+        let mut fto = StateFTO::solved();
+        change(&mut fto);
+        fto.validate()
+    }
+
+    #[test]
+    fn missing_pieces_are_found() {
+        assert_eq!(validated(|fto| fto.cp[1] = 0), Err(StateError::MissingCorner(1)));
+        assert_eq!(validated(|fto| fto.ep[4] = 5), Err(StateError::MissingEdge(4)));
+    }
+
+    #[test]
+    fn swapped_pieces_are_odd() {
+        assert_eq!(validated(|fto| fto.cp.swap(0, 1)), Err(StateError::OddCornerPermutation));
+        assert_eq!(validated(|fto| fto.ep.swap(2, 9)), Err(StateError::OddEdgePermutation));
+    }
+
+    #[test]
+    fn twisted_corner_is_found() {
+        assert_eq!(validated(|fto| fto.co[3] = false), Err(StateError::CornerTwisted(3)));
+        assert_eq!(validated(|fto| fto.co[..2].copy_from_slice(&[false, false])), Ok(()));
+    }
+
+    // Center Colors are counted by their Facelet Color, 0 to 3 in the first Orbit and 4 to 7 in the second one
+    #[test]
+    fn wrong_center_count_is_found() {
+        assert_eq!(validated(|fto| fto.ce1[0] = 1), Err(StateError::CenterCount(0, 2)));
+        assert_eq!(validated(|fto| fto.ce2[11] = 0), Err(StateError::CenterCount(4, 4)));
+    }
+
+    const SAMPLES: usize = 20_000;
+    // Chi Squared at p = 0.00001 by Degrees of Freedom, for many Tests at once
+    const CRITICAL: [f64; 32] = [
+        0.0, 19.5, 23.0, 25.9, 28.5, 30.9, 33.1, 35.3, 37.3, 39.3, 41.3, 43.2, 45.1, 46.9, 48.7, 50.5,
+        52.2, 54.0, 55.7, 57.4, 59.0, 60.7, 62.3, 64.0, 65.6, 67.2, 68.8, 70.3, 71.9, 73.5, 75.0, 76.6
+    ];
+
+    // Random Turns reach every State, with an odd or even Number of them
+    fn turned_states(rng: &mut StdRng) -> Vec<StateFTO> {
+        (0..SAMPLES).map(|_| {
+            let mut fto = StateFTO::solved();
+            for _ in 0..rng.random_range(100..102) {
+                fto.apply_turn(rng.random_range(0..StateFTO::NUM_TURNS));
+            }
+            fto
+        }).collect()
+    }
+
+    // Chi Squared of two Samples of the same Size, over the Categories of either
+    fn assert_same_distribution(counts: &[usize], other: &[usize], name: &str) {
+        let pairs: Vec<(f64, f64)> = counts.iter().zip(other).filter(|&(&count, &other)| count + other > 0).map(|(&count, &other)| (count as f64, other as f64)).collect();
+        let chi: f64 = pairs.iter().map(|&(count, other)| (count - other).powi(2) / (count + other)).sum();
+        assert!(chi < CRITICAL[pairs.len() - 1], "{name}: chi squared {chi} over {} categories", pairs.len());
+    }
+
+    fn color_counts(states: &[StateFTO]) -> Vec<[usize; 8]> {
+        let mut counts = vec![[0; 8]; 72];
+        for fto in states {
+            for (position, letter) in fto.to_facelets().chars().enumerate() {
+                counts[position][COLOR_LETTERS.iter().position(|&other| other == letter).unwrap()] += 1;
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn scrambled_stickers_match_turned_stickers() {
+        let mut rng = StdRng::seed_from_u64(25);
+        let scrambled: Vec<StateFTO> = (0..SAMPLES).map(|_| StateFTO::scrambled_with(&mut rng)).collect();
+        let turned = turned_states(&mut rng);
+        for (position, (counts, other)) in color_counts(&scrambled).iter().zip(color_counts(&turned)).enumerate() {
+            assert_same_distribution(counts, &other, &format!("sticker {position}"));
+        }
+    }
+
+    fn inversion_parity(arr: &[u8]) -> usize {
+        (0..arr.len()).map(|index| arr[index + 1..].iter().filter(|&&other| other < arr[index]).count()).sum::<usize>() & 1
+    }
+
+    // Parities of the first half of the Corners, the Edges and both Center Orbits, bucketed together,
+    // so that a Dependence between the Pieces shows up, the whole Permutations and Flips have fixed Parities
+    fn parity_counts(states: &[StateFTO]) -> [usize; 32] {
+        let mut counts = [0; 32];
+        for fto in states {
+            let flipped = fto.co[..3].iter().filter(|&&oriented| !oriented).count() & 1;
+            let parities = [inversion_parity(&fto.cp[..3]), flipped, inversion_parity(&fto.ep[..6]), inversion_parity(&fto.ce1), inversion_parity(&fto.ce2)];
+            counts[parities.iter().fold(0, |acc, &parity| acc * 2 + parity)] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn scrambled_parities_match_turned_parities() {
+        let mut rng = StdRng::seed_from_u64(25);
+        let scrambled: Vec<StateFTO> = (0..SAMPLES).map(|_| StateFTO::scrambled_with(&mut rng)).collect();
+        assert!(scrambled.iter().all(|fto| fto.validate().is_ok()));
+        let counts = parity_counts(&scrambled);
+        assert!(counts.iter().all(|&count| count > 0));
+        assert_same_distribution(&counts, &parity_counts(&turned_states(&mut rng)), "parities");
+    }
+}
@@ -1,11 +1,9 @@
-use std::sync::LazyLock;
+use rand::Rng;
 
-use crate::moving::turn::TurnTable;
+use crate::{error::Error, moving::turn::TurnTable, tables::SymTables};
 
 use super::{symhash::SymHash, *};
 
-pub static FTO2_SYMHASH_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateFTO2::get_symhash_table());
-pub static FTO2_TURN_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StateFTO2::get_turn_table());
 
 #[derive(Clone, Debug)]
 pub struct StateFTO2 {
@@ -15,7 +13,6 @@ pub struct StateFTO2 {
 }
 
 impl State for StateFTO2 {
-    const RAW_SIZE: usize = 360 * 32 * 12;
     const NUM_TURNS: u8 = 8 * 2;
     const NUM_SYMS: u8 = 48 * 2;
 
@@ -27,64 +24,6 @@ impl State for StateFTO2 {
         }
     }
 
-    fn from_hash(hash: usize) -> Self {
-        let mut cp = [6; 6];
-        let mut left = (hash / 12) >> 5;
-        let mut pieces = vec![5, 4, 3, 2, 1, 0];
-        let mut factor = 60;
-        let mut parity = 0;
-        for i in 0..4 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 5 - i;
-            parity += p;
-            cp[5 - i] = pieces.remove(p);
-        }
-        (cp[0], cp[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-
-        let mut co = [true; 6];
-        left = (hash / 12) & 31;
-        let mut even = true;
-        for i in 0..5 {
-            let o = left & 1 == 0;
-            left /= 2;
-            co[5 - i] = o;
-            even = even == o;
-        }
-        co[0] = even;
-
-        let mut ce = [4; 4];
-        left = hash % 12;
-        pieces = vec![3, 2, 1, 0];
-        factor = 3;
-        parity = 0;
-        for i in 0..2 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 3 - i;
-            parity += p;
-            ce[3 - i] = pieces.remove(p);
-        }
-        (ce[0], ce[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-        Self {
-            cp,
-            co,
-            ce
-        }
-    }
-
-    fn get_hash(&self) -> usize {
-        (hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)) * 12 + hash_permutation(&self.ce)
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (mut face, cw) = unwrap_turn(turn);
         if face >= 4 {
@@ -149,6 +88,68 @@ impl State for StateFTO2 {
     }
 }
 
+impl RawHash for StateFTO2 {
+    const RAW_SIZE: usize = 360 * 32 * 12;
+
+    fn from_hash(hash: usize) -> Self {
+        let mut cp = [6; 6];
+        let mut left = (hash / 12) >> 5;
+        let mut pieces = vec![5, 4, 3, 2, 1, 0];
+        let mut factor = 60;
+        let mut parity = 0;
+        for i in 0..4 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 5 - i;
+            parity += p;
+            cp[5 - i] = pieces.remove(p);
+        }
+        (cp[0], cp[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+
+        let mut co = [true; 6];
+        left = (hash / 12) & 31;
+        let mut even = true;
+        for i in 0..5 {
+            let o = left & 1 == 0;
+            left /= 2;
+            co[5 - i] = o;
+            even = even == o;
+        }
+        co[0] = even;
+
+        let mut ce = [4; 4];
+        left = hash % 12;
+        pieces = vec![3, 2, 1, 0];
+        factor = 3;
+        parity = 0;
+        for i in 0..2 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 3 - i;
+            parity += p;
+            ce[3 - i] = pieces.remove(p);
+        }
+        (ce[0], ce[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+        Self {
+            cp,
+            co,
+            ce
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        (hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)) * 12 + hash_permutation(&self.ce)
+    }
+}
+
 impl SymHash for StateFTO2 {
     const SYM_NAME: &str = "fto2";
     const SYM_SIZE: usize = 1_815;
@@ -158,19 +159,33 @@ impl TurnTable for StateFTO2 {
     const TURNTABLE_NAME: &str = "fto2";
     const NUM_HASHES: usize = Self::SYM_SIZE;
 
-    fn from_turntable_index(hash: usize) -> Self {
-        Self::from_sym_hash(&FTO2_SYMHASH_TABLE, hash)
+    type Index = SymTables;
+
+    fn from_turntable_index(index: &SymTables, hash: usize) -> Self {
+        Self::from_sym_hash(index.symhash(), hash)
     }
 
-    fn get_turntable_entry(&self) -> usize {
-        let (sym_hash, sym) = self.get_sym_hash_sym(&FTO2_SYMHASH_TABLE);
-        sym_hash * Self::NUM_SYMS as usize + sym as usize
+    fn get_turntable_entry(&self, index: &SymTables) -> Result<usize, Error> {
+        let (sym_hash, sym) = self.get_sym_hash_sym_indexed(index.symindex());
+        Ok(sym_hash * Self::NUM_SYMS as usize + sym as usize)
     }
 }
 
 impl StateFTO2 {
+    // Checks, that the State can be solved
+    pub fn validate(&self) -> Result<(), StateError> {
+        validate_permutation(&self.cp, StateError::MissingCorner, StateError::OddCornerPermutation)?;
+        validate_orientation(&self.co)?;
+        validate_permutation(&self.ce, StateError::MissingCenter, StateError::OddCenterPermutation)
+    }
+
     pub fn scrambled() -> Self {
-        Self::from_hash(rand::random_range(0..Self::RAW_SIZE))
+        Self::scrambled_with(&mut rand::rng())
+    }
+
+    // Uniform random State drawn from the given Generator, for reproducible Scrambles
+    pub fn scrambled_with<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_hash(rng.random_range(0..Self::RAW_SIZE))
     }
 
     pub fn get_parts(&self) -> (usize, usize, usize) {
@@ -272,4 +287,31 @@ impl StateFTO2 {
             new_ce
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::puzzle::Puzzle;
+
+    use super::*;
+
+    #[test]
+    fn turned_states_are_valid() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut fto2 = StateFTO2::solved();
+        for _ in 0..1000 {
+            fto2.apply_turn(rng.random_range(0..StateFTO2::NUM_MOVES));
+            assert_eq!(fto2.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn invalid_centers_are_found() {
+        let mut fto2 = StateFTO2::solved();
+        fto2.ce[2] = 0;
+        assert_eq!(fto2.validate(), Err(StateError::MissingCenter(2)));
+        fto2.ce = [1, 0, 2, 3];
+        assert_eq!(fto2.validate(), Err(StateError::OddCenterPermutation));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}};
+
+use super::{statefto::StateFTO, *};
+
+
+// Center Coordinate of one Orbit of the FTO, ce1 for ORBIT 1 and ce2 for ORBIT 2:
+// the Colors on the Positions of the Orbit, hashed like the Centers of the Pruning Tables
+// Syms with x2 swap the Orbits, so their Entries are the Hash in the other Orbit
+#[derive(Clone, Debug)]
+pub struct StateFTOCenters<const ORBIT: u8> {
+    ce: [u8; 12]
+}
+
+impl<const ORBIT: u8> State for StateFTOCenters<ORBIT> {
+    const NUM_TURNS: u8 = StateFTO::NUM_TURNS;
+    const NUM_SYMS: u8 = StateFTO::NUM_SYMS;
+
+    fn solved() -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn apply_turn(&mut self, turn: u8) {
+        let mut fto = self.to_fto();
+        fto.apply_turn(turn);
+        *self = Self::from_fto(&fto);
+    }
+
+    fn wrap_sym(&mut self, sym: u8) {
+        let mut fto = self.to_fto();
+        fto.wrap_sym(sym);
+        let (x2, ..) = unwrap_big_sym(sym);
+        self.ce = if (ORBIT == 1) != x2 {fto.ce1} else {fto.ce2};
+    }
+}
+
+impl<const ORBIT: u8> RawHash for StateFTOCenters<ORBIT> {
+    const RAW_SIZE: usize = 369_600;
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            ce: unhash_centers(hash)
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_centers(&self.ce)
+    }
+}
+
+impl<const ORBIT: u8> TurnTable for StateFTOCenters<ORBIT> {
+    const TURNTABLE_NAME: &str = if ORBIT == 1 {"ftoce1"} else {"ftoce2"};
+    const NUM_HASHES: usize = Self::RAW_SIZE;
+
+    type Index = ();
+
+    fn from_turntable_index(_index: &(), hash: usize) -> Self {
+        Self::from_hash(hash)
+    }
+
+    fn get_turntable_entry(&self, _index: &()) -> Result<usize, Error> {
+        Ok(self.get_hash())
+    }
+}
+
+impl<const ORBIT: u8> SymTable for StateFTOCenters<ORBIT> {
+    const SYMTABLE_NAME: &str = if ORBIT == 1 {"ftoce1"} else {"ftoce2"};
+}
+
+impl<const ORBIT: u8> StateFTOCenters<ORBIT> {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            ce: if ORBIT == 1 {fto.ce1} else {fto.ce2}
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        let mut fto = StateFTO::solved();
+        if ORBIT == 1 {
+            fto.ce1 = self.ce;
+        } else {
+            fto.ce2 = self.ce;
+        }
+        fto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    // The Orbit of the wrapped Centers, x2 swaps them like on the whole FTO
+    fn assert_wraps_like_fto<const ORBIT: u8, const OTHER: u8>() {
+        let mut rng = StdRng::seed_from_u64(22);
+        for _ in 0..20 {
+            let fto = StateFTO::scrambled_with(&mut rng);
+            for sym in 0..StateFTO::NUM_SYMS {
+                let mut wrapped_fto = fto.clone();
+                wrapped_fto.wrap_sym(sym);
+                let mut centers = StateFTOCenters::<ORBIT>::from_fto(&fto);
+                centers.wrap_sym(sym);
+                let (x2, ..) = unwrap_big_sym(sym);
+                let expected = if x2 {
+                    StateFTOCenters::<OTHER>::from_fto(&wrapped_fto).get_hash()
+                } else {
+                    StateFTOCenters::<ORBIT>::from_fto(&wrapped_fto).get_hash()
+                };
+                assert_eq!(centers.get_hash(), expected, "sym {sym}");
+            }
+        }
+    }
+
+    #[test]
+    fn first_orbit_wraps_like_fto() {
+        assert_wraps_like_fto::<1, 2>();
+    }
+
+    #[test]
+    fn second_orbit_wraps_like_fto() {
+        assert_wraps_like_fto::<2, 1>();
+    }
+}
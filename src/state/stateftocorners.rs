@@ -0,0 +1,85 @@
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}};
+
+use super::{statecorners::StateCorners, statefto::StateFTO, *};
+
+
+// Corner Coordinate of the FTO: Permutation and Orientation of the Corners
+#[derive(Clone, Debug)]
+pub struct StateFTOCorners {
+    cp: [u8; 6],
+    co: [bool; 6]
+}
+
+impl State for StateFTOCorners {
+    const NUM_TURNS: u8 = StateFTO::NUM_TURNS;
+    const NUM_SYMS: u8 = StateFTO::NUM_SYMS;
+
+    fn solved() -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn apply_turn(&mut self, turn: u8) {
+        let mut fto = self.to_fto();
+        fto.apply_turn(turn);
+        *self = Self::from_fto(&fto);
+    }
+
+    fn wrap_sym(&mut self, sym: u8) {
+        let mut fto = self.to_fto();
+        fto.wrap_sym(sym);
+        *self = Self::from_fto(&fto);
+    }
+}
+
+impl RawHash for StateFTOCorners {
+    const RAW_SIZE: usize = 11_520;
+
+    // Same Hash as the Corners of the FTO2
+    fn from_hash(hash: usize) -> Self {
+        let corners = StateCorners::from_hash(hash);
+        Self {
+            cp: corners.cp,
+            co: corners.co
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)
+    }
+}
+
+impl TurnTable for StateFTOCorners {
+    const TURNTABLE_NAME: &str = "ftocorners";
+    const NUM_HASHES: usize = Self::RAW_SIZE;
+
+    type Index = ();
+
+    fn from_turntable_index(_index: &(), hash: usize) -> Self {
+        Self::from_hash(hash)
+    }
+
+    fn get_turntable_entry(&self, _index: &()) -> Result<usize, Error> {
+        Ok(self.get_hash())
+    }
+}
+
+impl SymTable for StateFTOCorners {
+    const SYMTABLE_NAME: &str = "ftocorners";
+}
+
+impl StateFTOCorners {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            cp: fto.cp,
+            co: fto.co
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        StateFTO {
+            cp: self.cp,
+            co: self.co,
+            ..StateFTO::solved()
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::{statefto::StateFTO, *};
+
+
+// Edge Coordinate of the FTO: the even Permutation of the Edges
+// It has no Turn or Sym Tables, they would hold 239_500_800 Entries per Turn or Sym,
+// StateFTOEdgeTrio splits it into Coordinates with Tables
+#[derive(Clone, Debug)]
+pub struct StateFTOEdges {
+    ep: [u8; 12]
+}
+
+impl State for StateFTOEdges {
+    const NUM_TURNS: u8 = StateFTO::NUM_TURNS;
+    const NUM_SYMS: u8 = StateFTO::NUM_SYMS;
+
+    fn solved() -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn apply_turn(&mut self, turn: u8) {
+        let mut fto = self.to_fto();
+        fto.apply_turn(turn);
+        *self = Self::from_fto(&fto);
+    }
+
+    fn wrap_sym(&mut self, sym: u8) {
+        let mut fto = self.to_fto();
+        fto.wrap_sym(sym);
+        *self = Self::from_fto(&fto);
+    }
+}
+
+impl RawHash for StateFTOEdges {
+    const RAW_SIZE: usize = 239_500_800;
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            ep: unhash_permutation(hash)
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        hash_permutation(&self.ep)
+    }
+}
+
+impl StateFTOEdges {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self {
+            ep: fto.ep
+        }
+    }
+
+    fn to_fto(&self) -> StateFTO {
+        StateFTO {
+            ep: self.ep,
+            ..StateFTO::solved()
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{error::Error, moving::{sym::SymTable, turn::TurnTable}};
+
+use super::{statecorners::StateCorners, statefto::StateFTO, *};
+
+
+// Edges turned by D, B, L and R
+pub(crate) const EDGE_TRIOS: [[u8; 3]; 4] = [[4, 7, 10], [1, 8, 9], [0, 5, 6], [2, 3, 11]];
+pub(crate) const MAP_EP_TRIO: [u8; 12] = [2, 1, 3, 3, 0, 2, 2, 0, 1, 1, 0, 3];
+
+// Edge Coordinate of one Trio of the FTO, the Edges turned by D, B, L or R:
+// the Positions of the three Edges and their Order
+// With 1320 Hashes it has Turn and Sym Tables, unlike the whole Permutation
+// Its Syms are the Syms of the Corners, those without x2, and they move the Trio onto another Trio,
+// so their Entries are the Hash in that Trio
+#[derive(Clone, Debug)]
+pub struct StateFTOEdgeTrio<const TRIO: u8> {
+    edges: usize
+}
+
+impl<const TRIO: u8> State for StateFTOEdgeTrio<TRIO> {
+    const NUM_TURNS: u8 = StateFTO::NUM_TURNS;
+    const NUM_SYMS: u8 = StateCorners::NUM_SYMS;
+
+    fn solved() -> Self {
+        Self::from_fto(&StateFTO::solved())
+    }
+
+    fn apply_turn(&mut self, turn: u8) {
+        let mut fto = self.to_fto(TRIO);
+        fto.apply_turn(turn);
+        *self = Self::from_ep(&fto.ep, TRIO);
+    }
+
+    fn wrap_sym(&mut self, sym: u8) {
+        let mut fto = self.to_fto(TRIO);
+        fto.wrap_sym(sym << 1);
+        *self = Self::from_ep(&fto.ep, Self::get_sym_trio(sym));
+    }
+}
+
+impl<const TRIO: u8> RawHash for StateFTOEdgeTrio<TRIO> {
+    const RAW_SIZE: usize = 220 * 6;
+
+    fn from_hash(hash: usize) -> Self {
+        Self {
+            edges: hash
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        self.edges
+    }
+}
+
+impl<const TRIO: u8> TurnTable for StateFTOEdgeTrio<TRIO> {
+    const TURNTABLE_NAME: &str = match TRIO {
+        0 => "ftoedges0",
+        1 => "ftoedges1",
+        2 => "ftoedges2",
+        _ => "ftoedges3"
+    };
+    const NUM_HASHES: usize = Self::RAW_SIZE;
+
+    type Index = ();
+
+    fn from_turntable_index(_index: &(), hash: usize) -> Self {
+        Self::from_hash(hash)
+    }
+
+    fn get_turntable_entry(&self, _index: &()) -> Result<usize, Error> {
+        Ok(self.get_hash())
+    }
+}
+
+impl<const TRIO: u8> SymTable for StateFTOEdgeTrio<TRIO> {
+    const SYMTABLE_NAME: &str = Self::TURNTABLE_NAME;
+}
+
+impl<const TRIO: u8> StateFTOEdgeTrio<TRIO> {
+    pub fn from_fto(fto: &StateFTO) -> Self {
+        Self::from_ep(&fto.ep, TRIO)
+    }
+
+    // Trio, onto which the Sym moves the Edges of this Trio
+    pub fn get_sym_trio(sym: u8) -> u8 {
+        let trio = EDGE_TRIOS[TRIO as usize];
+        // The Sym moves a Cycle of the Trio onto a Cycle of the other Trio
+        let mut fto = StateFTO::solved();
+        fto.ep[trio[0] as usize] = trio[1];
+        fto.ep[trio[1] as usize] = trio[2];
+        fto.ep[trio[2] as usize] = trio[0];
+        fto.wrap_sym(sym << 1);
+        (0..12).find(|&position| fto.ep[position] != position as u8).map_or(TRIO, |position| MAP_EP_TRIO[fto.ep[position] as usize])
+    }
+
+    // Positions of the Edges of the Trio and the Order of the Edges by their Positions
+    fn from_ep(ep: &[u8; 12], trio: u8) -> Self {
+        let trio = EDGE_TRIOS[trio as usize];
+        let order: Vec<usize> = ep.iter().filter_map(|edge| trio.iter().position(|other| other == edge)).collect();
+        Self {
+            edges: hash_combination(&ep.map(|edge| trio.contains(&edge))) * 6 + order[0] * 2 + (order[1] > order[2]) as usize
+        }
+    }
+
+    // The other Edges fill the remaining Positions
+    pub(crate) fn to_fto(&self, trio: u8) -> StateFTO {
+        let trio = EDGE_TRIOS[trio as usize];
+        let mut left = trio.to_vec();
+        let first = left.remove(self.edges % 6 / 2);
+        let order = if self.edges & 1 == 0 {[first, left[0], left[1]]} else {[first, left[1], left[0]]};
+        let mut others = (0..12u8).filter(|edge| !trio.contains(edge));
+        let mut placed = 0;
+        let ep = unhash_combination::<12>(self.edges / 6, 3).map(|set| if set {
+            placed += 1;
+            order[placed - 1]
+        } else {
+            others.next().unwrap()
+        });
+        StateFTO {
+            ep,
+            ..StateFTO::solved()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    // The wrapped Trio is the Trio of the wrapped FTO, which the Sym moves it onto
+    fn assert_wraps_like_fto<const TRIO: u8>() {
+        let mut rng = StdRng::seed_from_u64(22);
+        for _ in 0..20 {
+            let fto = StateFTO::scrambled_with(&mut rng);
+            for sym in 0..StateFTOEdgeTrio::<TRIO>::NUM_SYMS {
+                let mut wrapped_fto = fto.clone();
+                wrapped_fto.wrap_sym(sym << 1);
+                let sym_trio = StateFTOEdgeTrio::<TRIO>::get_sym_trio(sym);
+                // The Sym moves all three Edges of the Trio onto the Edges of one Trio
+                let trio = EDGE_TRIOS[TRIO as usize];
+                let mut cycled = StateFTO::solved();
+                cycled.ep[trio[0] as usize] = trio[1];
+                cycled.ep[trio[1] as usize] = trio[2];
+                cycled.ep[trio[2] as usize] = trio[0];
+                cycled.wrap_sym(sym << 1);
+                let moved: Vec<u8> = (0..12).filter(|&position| cycled.ep[position] != position as u8).map(|position| MAP_EP_TRIO[cycled.ep[position] as usize]).collect();
+                assert_eq!(moved, [sym_trio; 3], "sym {sym}");
+                let mut edges = StateFTOEdgeTrio::<TRIO>::from_fto(&fto);
+                edges.wrap_sym(sym);
+                assert_eq!(edges.get_hash(), StateFTOEdgeTrio::<TRIO>::from_ep(&wrapped_fto.ep, sym_trio).get_hash(), "sym {sym}");
+            }
+        }
+    }
+
+    #[test]
+    fn trios_wrap_like_fto() {
+        assert_wraps_like_fto::<0>();
+        assert_wraps_like_fto::<1>();
+        assert_wraps_like_fto::<2>();
+        assert_wraps_like_fto::<3>();
+    }
+
+    fn assert_round_trip<const TRIO: u8>() {
+        for hash in 0..StateFTOEdgeTrio::<TRIO>::RAW_SIZE {
+            let fto = StateFTOEdgeTrio::<TRIO>::from_hash(hash).to_fto(TRIO);
+            assert_eq!(StateFTOEdgeTrio::<TRIO>::from_fto(&fto).get_hash(), hash, "trio {TRIO}");
+        }
+    }
+
+    #[test]
+    fn edge_codes_round_trip() {
+        assert_round_trip::<0>();
+        assert_round_trip::<1>();
+        assert_round_trip::<2>();
+        assert_round_trip::<3>();
+    }
+
+    #[test]
+    fn trio_turns_like_fto() {
+        let mut rng = StdRng::seed_from_u64(22);
+        let fto = StateFTO::scrambled_with(&mut rng);
+        for turn in 0..StateFTO::NUM_TURNS {
+            let mut turned_fto = fto.clone();
+            turned_fto.apply_turn(turn);
+            let mut edges = StateFTOEdgeTrio::<1>::from_fto(&fto);
+            edges.apply_turn(turn);
+            assert_eq!(edges.get_hash(), StateFTOEdgeTrio::<1>::from_fto(&turned_fto).get_hash(), "turn {turn}");
+        }
+    }
+}
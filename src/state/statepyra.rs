@@ -1,11 +1,9 @@
-use std::sync::LazyLock;
+use rand::Rng;
 
-use crate::moving::turn::TurnTable;
+use crate::{error::Error, moving::turn::TurnTable, tables::SymTables};
 
 use super::{statefto::StateFTO, symhash::SymHash, *};
 
-pub static PYRA_SYMHASH_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StatePyra::get_symhash_table());
-pub static PYRA_TURN_TABLE: LazyLock<Vec<usize>> = LazyLock::new(|| StatePyra::get_turn_table());
 
 #[derive(Clone)]
 pub struct StatePyra {
@@ -15,8 +13,6 @@ pub struct StatePyra {
 }
 
 impl State for StatePyra {
-    const RAW_SIZE: usize = 360 * 32 * 81;
-
     const NUM_TURNS: u8 = 8 * 2;
 
     const NUM_SYMS: u8 = 24 * 2;
@@ -29,54 +25,6 @@ impl State for StatePyra {
         }
     }
 
-    fn from_hash(hash: usize) -> Self {
-        let mut cp = [6; 6];
-        let mut left = (hash / 81) >> 5;
-        let mut pieces = vec![5, 4, 3, 2, 1, 0];
-        let mut factor = 60;
-        let mut parity = 0;
-        for i in 0..4 {
-            let p = left / factor;
-            left %= factor;
-            factor /= 5 - i;
-            parity += p;
-            cp[5 - i] = pieces.remove(p);
-        }
-        (cp[0], cp[1]) = if parity & 1 == 0 {
-            (pieces[1], pieces[0])
-        } else {
-            (pieces[0], pieces[1])
-        };
-
-        let mut co = [true; 6];
-        left = (hash / 81) & 31;
-        let mut even = true;
-        for i in 0..5 {
-            let o = left & 1 == 0;
-            left /= 2;
-            co[5 - i] = o;
-            even = even == o;
-        }
-        co[0] = even;
-
-        let mut ct = ID_CT;
-        let mut left = (hash % 81) as u8;
-        for i in 0..4 {
-            ct[3 - i] = left % 3;
-            left /= 3;
-        }
-
-        Self {
-            cp,
-            co,
-            ct
-        }
-    }
-
-    fn get_hash(&self) -> usize {
-        (hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)) * 81 + hash_center_turn(&self.ct)
-    }
-
     fn apply_turn(&mut self, turn: u8) {
         let (mut face, cw) = unwrap_turn(turn);
         if face >= 4 {
@@ -138,6 +86,58 @@ impl State for StatePyra {
     }
 }
 
+impl RawHash for StatePyra {
+    const RAW_SIZE: usize = 360 * 32 * 81;
+
+    fn from_hash(hash: usize) -> Self {
+        let mut cp = [6; 6];
+        let mut left = (hash / 81) >> 5;
+        let mut pieces = vec![5, 4, 3, 2, 1, 0];
+        let mut factor = 60;
+        let mut parity = 0;
+        for i in 0..4 {
+            let p = left / factor;
+            left %= factor;
+            factor /= 5 - i;
+            parity += p;
+            cp[5 - i] = pieces.remove(p);
+        }
+        (cp[0], cp[1]) = if parity & 1 == 0 {
+            (pieces[1], pieces[0])
+        } else {
+            (pieces[0], pieces[1])
+        };
+
+        let mut co = [true; 6];
+        left = (hash / 81) & 31;
+        let mut even = true;
+        for i in 0..5 {
+            let o = left & 1 == 0;
+            left /= 2;
+            co[5 - i] = o;
+            even = even == o;
+        }
+        co[0] = even;
+
+        let mut ct = ID_CT;
+        let mut left = (hash % 81) as u8;
+        for i in 0..4 {
+            ct[3 - i] = left % 3;
+            left /= 3;
+        }
+
+        Self {
+            cp,
+            co,
+            ct
+        }
+    }
+
+    fn get_hash(&self) -> usize {
+        (hash_permutation(&self.cp) * 32 + hash_orientation(&self.co)) * 81 + hash_center_turn(&self.ct)
+    }
+}
+
 impl SymHash for StatePyra {
     const SYM_NAME: &str = "pyra";
     const SYM_SIZE: usize = 21073;
@@ -147,58 +147,75 @@ impl TurnTable for StatePyra {
     const TURNTABLE_NAME: &str = "pyra";
     const NUM_HASHES: usize = Self::SYM_SIZE;
 
-    fn from_turntable_index(hash: usize) -> Self {
-        Self::from_sym_hash(&PYRA_SYMHASH_TABLE, hash)
+    type Index = SymTables;
+
+    fn from_turntable_index(index: &SymTables, hash: usize) -> Self {
+        Self::from_sym_hash(index.symhash(), hash)
     }
 
-    fn get_turntable_entry(&self) -> usize {
-        let (sym_hash, sym) = self.get_sym_hash_sym(&PYRA_SYMHASH_TABLE);
-        sym_hash * Self::NUM_SYMS as usize + sym as usize
+    fn get_turntable_entry(&self, index: &SymTables) -> Result<usize, Error> {
+        let (sym_hash, sym) = self.get_sym_hash_sym_indexed(index.symindex());
+        Ok(sym_hash * Self::NUM_SYMS as usize + sym as usize)
     }
 }
 
 impl StatePyra {
+    // Checks, that the State can be solved
+    pub fn validate(&self) -> Result<(), StateError> {
+        validate_permutation(&self.cp, StateError::MissingCorner, StateError::OddCornerPermutation)?;
+        validate_orientation(&self.co)?;
+        match self.ct.iter().position(|&twist| twist >= 3) {
+            Some(center) => Err(StateError::CenterTwist(center as u8)),
+            None => Ok(())
+        }
+    }
+
     pub fn scrambled() -> Self {
-        Self::from_hash(rand::random_range(0..Self::RAW_SIZE))
+        Self::scrambled_with(&mut rand::rng())
+    }
+
+    // Uniform random State drawn from the given Generator, for reproducible Scrambles
+    pub fn scrambled_with<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from_hash(rng.random_range(0..Self::RAW_SIZE))
     }
 
     pub fn get_parts(&self) -> ([u8; 6], [bool; 6], [u8; 4]) {
         (self.cp, self.co, self.ct)
     }
 
-    pub fn from_fto(fto: &StateFTO) -> Self {
+    // Fails, if an Edge of the FTO is off the Pyra Subgroup
+    pub fn from_fto(fto: &StateFTO) -> Result<Self, Error> {
         let ct = [
             match fto.ep[4] {
                 4 => 0,
                 7 => 1,
                 10 => 2,
-                _ => panic!("FTO not in Pyra")
+                _ => return Err(Error::NotInSubgroup("Pyra"))
             },
             match fto.ep[1] {
                 1 => 0,
                 9 => 1,
                 8 => 2,
-                _ => panic!("FTO not in Pyra")
+                _ => return Err(Error::NotInSubgroup("Pyra"))
             },
             match fto.ep[0] {
                 0 => 0,
                 6 => 1,
                 5 => 2,
-                _ => panic!("FTO not in Pyra")
+                _ => return Err(Error::NotInSubgroup("Pyra"))
             },
             match fto.ep[2] {
                 2 => 0,
                 3 => 1,
                 11 => 2,
-                _ => panic!("FTO not in Pyra")
+                _ => return Err(Error::NotInSubgroup("Pyra"))
             }
         ];
-        Self {
+        Ok(Self {
             cp: fto.cp,
             co: fto.co,
             ct
-        }
-
+        })
     }
 
     fn apply(&mut self, cp: [u8; 6], co: [bool; 6], ct: [u8; 4]) {
@@ -269,4 +286,29 @@ impl StatePyra {
             };
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::puzzle::Puzzle;
+
+    use super::*;
+
+    #[test]
+    fn turned_states_are_valid() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut pyra = StatePyra::solved();
+        for _ in 0..1000 {
+            pyra.apply_turn(rng.random_range(0..StatePyra::NUM_MOVES));
+            assert_eq!(pyra.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn invalid_center_twist_is_found() {
+        let mut pyra = StatePyra::solved();
+        pyra.ct[1] = 3;
+        assert_eq!(pyra.validate(), Err(StateError::CenterTwist(1)));
+    }
+}
@@ -1,28 +1,17 @@
-use std::{fs, time::Instant};
+use std::{io::ErrorKind, path::Path, time::Instant};
 
-use bytemuck::cast_slice;
-use indicatif::{ProgressBar, ProgressStyle};
 use rayon::{iter::{IntoParallelIterator, ParallelIterator}, slice::ParallelSliceMut};
 
-use crate::pruning::format_duration;
+use crate::{error::Error, progress::ProgressSink, tablefile::{get_table_file, read_hash_table, write_hash_table, TableKind}};
 
-use super::State;
+use super::RawHash;
 
-pub trait SymHash: State {
+pub trait SymHash: RawHash {
     const SYM_NAME: &str;
     const SYM_SIZE: usize;
 
-    fn get_symhash_table() -> Vec<usize> {
-        match Self::load_table() {
-            Ok(table) => table,
-            Err(_) => {
-                Self::generate_table()
-            }
-        }
-    }
-
-    fn from_sym_hash(table: &Vec<usize>, sym_hash: usize) -> Self {
-        Self::from_hash(table[sym_hash])
+    fn from_sym_hash(table: &[u32], sym_hash: usize) -> Self {
+        Self::from_hash(table[sym_hash] as usize)
     }
 
     fn get_syms(&self) -> Vec<u8> {
@@ -30,7 +19,7 @@ pub trait SymHash: State {
         self.get_sym_states().into_iter().enumerate().filter_map(|(sym, state)| if state.get_hash() == hash {Some(sym as u8)} else {None}).collect()
     }
 
-    fn get_sym_hash(&self, table: &Vec<usize>) -> usize {
+    fn get_sym_hash(&self, table: &[u32]) -> Result<usize, Error> {
         Self::get_sym_hash_from_repr(table, self.get_repr())
     }
 
@@ -38,49 +27,81 @@ pub trait SymHash: State {
         self.get_sym_states().iter().map(|state| state.get_hash()).min().unwrap()
     }
 
-    fn get_sym_hash_from_repr(table: &Vec<usize>, repr: usize) -> usize {
-        table.iter().position(|&other| repr == other).expect("Representant not in Array")
+    // Binary Search on the sorted Representants, fails if the Table doesn't belong to the State
+    fn get_sym_hash_from_repr(table: &[u32], repr: usize) -> Result<usize, Error> {
+        u32::try_from(repr).ok().and_then(|repr| table.binary_search(&repr).ok()).ok_or(Error::MissingRepresentant(repr))
     }
 
-    fn get_sym_hash_sym(&self, table: &Vec<usize>) -> (usize, u8) {
+    fn get_sym_hash_sym(&self, table: &[u32]) -> Result<(usize, u8), Error> {
         let (repr, sym) = self.get_sym_states().into_iter().enumerate().map(|(sym, state)| (state.get_hash(), sym as u8)).min_by_key(|(state, _)| *state).unwrap();
-        (Self::get_sym_hash_from_repr(table, repr), sym)
+        Ok((Self::get_sym_hash_from_repr(table, repr)?, sym))
+    }
+
+    // Looks up the Sym Hash and Sym of the State without computing its symmetric States
+    fn get_sym_hash_sym_indexed(&self, index: &[u32]) -> (usize, u8) {
+        let entry = index[self.get_hash()] as usize;
+        (entry / Self::NUM_SYMS as usize, (entry % Self::NUM_SYMS as usize) as u8)
+    }
+
+    fn get_sym_hash_indexed(&self, index: &[u32]) -> usize {
+        index[self.get_hash()] as usize / Self::NUM_SYMS as usize
     }
 
-    fn generate_table() -> Vec<usize> {
-        println!("Generating table {}", Self::get_file_name());
+    fn generate_table(dir: &Path, progress: &dyn ProgressSink) -> Result<Vec<u32>, Error> {
+        progress.start(&Self::get_file_name(dir), Self::RAW_SIZE as u64);
         let now = Instant::now();
-        let bar = ProgressBar::new(Self::RAW_SIZE as u64);
-        bar.set_style(ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {pos:>10}/{len:10}").unwrap().progress_chars("#>-"));
-        let mut table: Vec<usize> = (0..Self::RAW_SIZE).into_par_iter().map(|raw_hash| {
-            bar.inc(1);
-            Self::from_hash(raw_hash).get_repr()
+        let mut table: Vec<u32> = (0..Self::RAW_SIZE).into_par_iter().map(|raw_hash| {
+            progress.entries_filled(1);
+            Self::from_hash(raw_hash).get_repr() as u32
         }).collect();
         table.par_sort();
         table.dedup();
         let elapsed = now.elapsed();
-        bar.finish();
-        println!("Finished generating Table in {}", format_duration(elapsed));
-        println!("Size: {}", table.len());
-        Self::save_table(&table);
-        table
+        progress.done(elapsed);
+        progress.message(&format!("Size: {}", table.len()));
+        Self::save_table(dir, &table)?;
+        progress.message(&format!("Saved table {}", Self::get_file_name(dir)));
+        Ok(table)
     }
 
-    fn save_table(table: &Vec<usize>) {
-        match fs::write(Self::get_file_name(), cast_slice(table)) {
-            Ok(_) => println!("Saved table {}", Self::get_file_name()),
-            Err(_) => println!("Couldn't write table {}", Self::get_file_name())
-        }
+    fn save_table(dir: &Path, table: &[u32]) -> Result<(), Error> {
+        write_hash_table(&Self::get_file_name(dir), TableKind::SymHash, Self::SYM_NAME, table)?;
+        Ok(())
     }
 
-    fn load_table() -> Result<Vec<usize>, std::io::Error> {
-        match fs::read(Self::get_file_name()) {
-            Ok(data) => Ok(cast_slice(&data).to_vec()),
-            Err(err) => Err(err)
+    // Lookups need the Representants sorted
+    fn load_table(dir: &Path) -> Result<Vec<u32>, std::io::Error> {
+        let table = read_hash_table(&Self::get_file_name(dir), TableKind::SymHash, Self::SYM_NAME, Self::SYM_SIZE)?;
+        if !table.is_sorted() {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, format!("Invalid table {}: representants not sorted", Self::get_file_name(dir))));
         }
+        Ok(table)
+    }
+
+    fn get_file_name(dir: &Path) -> String {
+        get_table_file(dir, "symhash", &format!("{}.bin", Self::SYM_NAME))
+    }
+
+    // Index from raw Hashes to sym_hash * NUM_SYMS + sym, the same Entries as in the Turn Tables
+    fn generate_index(table: &[u32], dir: &Path, progress: &dyn ProgressSink) -> Result<Vec<u32>, Error> {
+        progress.start(&Self::get_index_file_name(dir), Self::RAW_SIZE as u64);
+        let now = Instant::now();
+        let index = (0..Self::RAW_SIZE).into_par_iter().map(|raw_hash| {
+            progress.entries_filled(1);
+            let (sym_hash, sym) = Self::from_hash(raw_hash).get_sym_hash_sym(table)?;
+            Ok((sym_hash * Self::NUM_SYMS as usize + sym as usize) as u32)
+        }).collect::<Result<Vec<u32>, Error>>()?;
+        progress.done(now.elapsed());
+        write_hash_table(&Self::get_index_file_name(dir), TableKind::SymIndex, Self::SYM_NAME, &index)?;
+        progress.message(&format!("Saved table {}", Self::get_index_file_name(dir)));
+        Ok(index)
+    }
+
+    fn load_index(dir: &Path) -> Result<Vec<u32>, std::io::Error> {
+        read_hash_table(&Self::get_index_file_name(dir), TableKind::SymIndex, Self::SYM_NAME, Self::RAW_SIZE)
     }
 
-    fn get_file_name() -> String {
-        format!("tables/symhash/{}.bin", Self::SYM_NAME)
+    fn get_index_file_name(dir: &Path) -> String {
+        get_table_file(dir, "symindex", &format!("{}.bin", Self::SYM_NAME))
     }
 }
\ No newline at end of file
@@ -0,0 +1,359 @@
+use std::{env, fs::{self, File}, io::{Error, ErrorKind, Write}, ops::Deref, path::{Path, PathBuf}, process, sync::atomic::{AtomicU64, Ordering}};
+
+use memmap2::Mmap;
+
+
+// Every Table File starts with a Header describing its Entries:
+// Magic, Version, Kind, Bits per Entry, Entry Count, CRC32 of the Data, Name Length, Name
+// All Numbers are little endian, the Data follows the Header
+const MAGIC: [u8; 4] = *b"FTOT";
+const VERSION: u16 = 1;
+const HEADER_SIZE: usize = 4 + 2 + 1 + 1 + 8 + 4 + 2;
+
+// Numbers the temporary Files of the Process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Environment Variable for the Directory of the Tables
+pub const TABLE_DIR_VAR: &str = "FTO_SOLVER_TABLES";
+
+// The Environment Variable, else "tables" in the working Directory
+pub fn get_table_dir() -> PathBuf {
+    env::var_os(TABLE_DIR_VAR).map_or_else(|| PathBuf::from("tables"), PathBuf::from)
+}
+
+// Path of a Table File in one of the Subdirectories pruning, sym, turn, symhash and symindex
+pub fn get_table_file(dir: &Path, subdir: &str, file: &str) -> String {
+    dir.join(subdir).join(file).to_string_lossy().into_owned()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableKind {
+    Pruning,
+    Turn,
+    Sym,
+    SymHash,
+    Checkpoint,
+    SymIndex
+}
+
+impl TableKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Pruning => 0,
+            Self::Turn => 1,
+            Self::Sym => 2,
+            Self::SymHash => 3,
+            Self::Checkpoint => 4,
+            Self::SymIndex => 5
+        }
+    }
+}
+
+// Expected Contents of a Table File
+pub struct TableHeader<'a> {
+    pub kind: TableKind,
+    pub name: &'a str,
+    pub entry_bits: u8,
+    pub count: u64
+}
+
+impl TableHeader<'_> {
+    fn data_len(&self) -> usize {
+        (self.count as usize * self.entry_bits as usize).div_ceil(8)
+    }
+
+    fn to_bytes(&self, crc: u32) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.name.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.push(self.kind.to_byte());
+        bytes.push(self.entry_bits);
+        bytes.extend_from_slice(&self.count.to_le_bytes());
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        bytes.extend_from_slice(&(self.name.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(self.name.as_bytes());
+        bytes
+    }
+
+    // Checks the Header of the File and returns where its Data starts
+    fn check(&self, file: &[u8]) -> Result<usize, String> {
+        let start = self.check_without_crc(file)?;
+        let crc = u32::from_le_bytes(file[16..20].try_into().unwrap());
+        if crc32fast::hash(&file[start..]) != crc {
+            return Err(String::from("checksum mismatch"));
+        }
+        Ok(start)
+    }
+
+    fn check_without_crc(&self, file: &[u8]) -> Result<usize, String> {
+        if file.len() < HEADER_SIZE || file[0..4] != MAGIC {
+            return Err(String::from("missing table header"));
+        }
+        let version = u16::from_le_bytes([file[4], file[5]]);
+        if version != VERSION {
+            return Err(format!("version {version}, expected {VERSION}"));
+        }
+        if file[6] != self.kind.to_byte() {
+            return Err(String::from("wrong table kind"));
+        }
+        if file[7] != self.entry_bits {
+            return Err(format!("{} bits per entry, expected {}", file[7], self.entry_bits));
+        }
+        let count = u64::from_le_bytes(file[8..16].try_into().unwrap());
+        if count != self.count {
+            return Err(format!("{count} entries, expected {}", self.count));
+        }
+        let name_len = u16::from_le_bytes([file[20], file[21]]) as usize;
+        let name = file.get(HEADER_SIZE..HEADER_SIZE + name_len).ok_or("truncated header")?;
+        if name != self.name.as_bytes() {
+            return Err(format!("table {}, expected {}", String::from_utf8_lossy(name), self.name));
+        }
+        let data = &file[HEADER_SIZE + name_len..];
+        if data.len() != self.data_len() {
+            return Err(format!("{} bytes of data, expected {}", data.len(), self.data_len()));
+        }
+        Ok(HEADER_SIZE + name_len)
+    }
+}
+
+pub fn write_table(file_name: &str, header: &TableHeader, data: &[u8]) -> Result<(), Error> {
+    assert_eq!(data.len(), header.data_len(), "Data doesn't match the Header of {file_name}");
+    write_table_file(file_name, header, data).map_err(|err| Error::new(err.kind(), format!("Couldn't write table {file_name}: {err}")))
+}
+
+fn write_table_file(file_name: &str, header: &TableHeader, data: &[u8]) -> Result<(), Error> {
+    if let Some(dir) = Path::new(file_name).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // Replaces the File at once, so that mapped Tables of other Processes stay intact
+    // Every Writer has its own temporary File, so that concurrent Generators don't mix their Data
+    let temp_name = format!("{file_name}.{}-{}.tmp", process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed));
+    let written = File::create(&temp_name).and_then(|mut file| {
+        file.write_all(&header.to_bytes(crc32fast::hash(data)))?;
+        file.write_all(data)
+    }).and_then(|_| fs::rename(&temp_name, file_name));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_name);
+    }
+    written
+}
+
+// Rejects Files, whose Header doesn't match or whose Data is truncated or corrupted
+pub fn read_table(file_name: &str, header: &TableHeader) -> Result<Vec<u8>, Error> {
+    strip_header(file_name, fs::read(file_name)?, header)
+}
+
+fn strip_header(file_name: &str, mut file: Vec<u8>, header: &TableHeader) -> Result<Vec<u8>, Error> {
+    match header.check(&file) {
+        Ok(start) => {
+            file.drain(..start);
+            Ok(file)
+        }
+        Err(reason) => Err(invalid_table(file_name, reason))
+    }
+}
+
+// Reads a Table, whose Entry Count is only known from its Header
+pub fn read_table_any_count(file_name: &str, kind: TableKind, name: &str, entry_bits: u8) -> Result<Vec<u8>, Error> {
+    let file = fs::read(file_name)?;
+    let count = file.get(8..16).map_or(0, |bytes| u64::from_le_bytes(bytes.try_into().unwrap()));
+    strip_header(file_name, file, &TableHeader { kind, name, entry_bits, count })
+}
+
+// Read only Table mapped into memory, so that Processes share it through the Page Cache
+pub struct MappedTable {
+    mmap: Mmap,
+    start: usize
+}
+
+impl Deref for MappedTable {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap[self.start..]
+    }
+}
+
+// The Checksum isn't verified, since that would read the whole Table on startup
+pub fn map_table(file_name: &str, header: &TableHeader) -> Result<MappedTable, Error> {
+    let file = File::open(file_name)?;
+    // Tables are never written in place, see write_table
+    let mmap = unsafe { Mmap::map(&file)? };
+    match header.check_without_crc(&mmap) {
+        Ok(start) => Ok(MappedTable { mmap, start }),
+        Err(reason) => Err(invalid_table(file_name, reason))
+    }
+}
+
+// Tables of Hashes are stored as u32 Entries
+pub fn write_hash_table(file_name: &str, kind: TableKind, name: &str, table: &[u32]) -> Result<(), Error> {
+    let data: Vec<u8> = table.iter().flat_map(|entry| entry.to_le_bytes()).collect();
+    write_table(file_name, &TableHeader { kind, name, entry_bits: 32, count: table.len() as u64 }, &data)
+}
+
+// Also reads the older Files without Header, which hold the native usize Entries,
+// and rewrites them with a Header
+pub fn read_hash_table(file_name: &str, kind: TableKind, name: &str, count: usize) -> Result<Vec<u32>, Error> {
+    let file = fs::read(file_name)?;
+    if !file.starts_with(&MAGIC) && file.len() == count * size_of::<usize>() {
+        let table = file.chunks_exact(size_of::<usize>()).map(|bytes| {
+            u32::try_from(usize::from_ne_bytes(bytes.try_into().unwrap())).map_err(|_| invalid_table(file_name, String::from("entry exceeds u32")))
+        }).collect::<Result<Vec<u32>, Error>>()?;
+        write_hash_table(file_name, kind, name, &table)?;
+        return Ok(table);
+    }
+    let header = TableHeader { kind, name, entry_bits: 32, count: count as u64 };
+    let start = header.check(&file).map_err(|reason| invalid_table(file_name, reason))?;
+    Ok(file[start..].chunks_exact(4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap())).collect())
+}
+
+fn invalid_table(file_name: &str, reason: String) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("Invalid table {file_name}: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: TableHeader = TableHeader {
+        kind: TableKind::Turn,
+        name: "test",
+        entry_bits: 8,
+        count: 4
+    };
+
+    // File in a Directory of the Test, which is removed with it
+    struct TempFile {
+        dir: PathBuf
+    }
+
+    impl TempFile {
+        fn new(test: &str) -> Self {
+            let dir = env::temp_dir().join(format!("fto-solver-tablefile-{test}-{}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            Self {
+                dir
+            }
+        }
+
+        fn name(&self) -> String {
+            get_table_file(&self.dir, "turn", "test.bin")
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    // Writes a valid Table and changes its Bytes, reading it has to fail with InvalidData
+    fn assert_rejected(test: &str, change: impl FnOnce(&mut Vec<u8>)) {
+        let file = TempFile::new(test);
+        write_table(&file.name(), &HEADER, &[1, 2, 3, 4]).unwrap();
+        let mut bytes = fs::read(file.name()).unwrap();
+        change(&mut bytes);
+        fs::write(file.name(), bytes).unwrap();
+        let err = read_table(&file.name(), &HEADER).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{err}");
+    }
+
+    #[test]
+    fn written_table_is_read() {
+        let file = TempFile::new("read");
+        write_table(&file.name(), &HEADER, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(read_table(&file.name(), &HEADER).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(&*map_table(&file.name(), &HEADER).unwrap(), [1, 2, 3, 4]);
+    }
+
+    // Concurrent Writers of the same Table each rename a complete File
+    #[test]
+    fn concurrent_writes_leave_a_valid_table() {
+        let file = TempFile::new("concurrent");
+        std::thread::scope(|scope| {
+            for value in 0..8 {
+                let name = file.name();
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        write_table(&name, &HEADER, &[value; 4]).unwrap();
+                    }
+                });
+            }
+        });
+        let table = read_table(&file.name(), &HEADER).unwrap();
+        assert!(table.iter().all(|&entry| entry == table[0]));
+        assert_eq!(fs::read_dir(Path::new(&file.name()).parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn wrong_magic_is_rejected() {
+        assert_rejected("magic", |bytes| bytes[0] = b'X');
+    }
+
+    #[test]
+    fn wrong_version_is_rejected() {
+        assert_rejected("version", |bytes| bytes[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes()));
+    }
+
+    #[test]
+    fn wrong_kind_is_rejected() {
+        assert_rejected("kind", |bytes| bytes[6] = TableKind::Sym.to_byte());
+    }
+
+    #[test]
+    fn wrong_name_is_rejected() {
+        assert_rejected("name", |bytes| bytes[HEADER_SIZE] = b'b');
+    }
+
+    #[test]
+    fn wrong_crc_is_rejected() {
+        assert_rejected("crc", |bytes| *bytes.last_mut().unwrap() ^= 1);
+    }
+
+    #[test]
+    fn written_hash_table_is_read() {
+        let file = TempFile::new("hash");
+        write_hash_table(&file.name(), TableKind::Turn, "test", &[1, 2, 3, u32::MAX]).unwrap();
+        assert_eq!(read_hash_table(&file.name(), TableKind::Turn, "test", 4).unwrap(), [1, 2, 3, u32::MAX]);
+    }
+
+    // Writes the Entries without Header, like the Tables of older Versions
+    fn write_headerless(file: &TempFile, table: &[usize]) {
+        let data: Vec<u8> = table.iter().flat_map(|entry| entry.to_ne_bytes()).collect();
+        fs::create_dir_all(Path::new(&file.name()).parent().unwrap()).unwrap();
+        fs::write(file.name(), data).unwrap();
+    }
+
+    #[test]
+    fn table_without_header_is_converted() {
+        let file = TempFile::new("headerless");
+        write_headerless(&file, &[1, 2, 3, u32::MAX as usize]);
+        assert_eq!(read_hash_table(&file.name(), TableKind::Turn, "test", 4).unwrap(), [1, 2, 3, u32::MAX]);
+        assert!(fs::read(file.name()).unwrap().starts_with(&MAGIC));
+        assert_eq!(read_hash_table(&file.name(), TableKind::Turn, "test", 4).unwrap(), [1, 2, 3, u32::MAX]);
+    }
+
+    #[test]
+    fn table_without_header_exceeding_u32_is_rejected() {
+        let file = TempFile::new("headerless-large");
+        write_headerless(&file, &[1, 2, 3, u32::MAX as usize + 1]);
+        let err = read_hash_table(&file.name(), TableKind::Turn, "test", 4).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{err}");
+    }
+
+    #[test]
+    fn table_without_header_of_wrong_size_is_rejected() {
+        let file = TempFile::new("headerless-size");
+        write_headerless(&file, &[1, 2, 3]);
+        let err = read_hash_table(&file.name(), TableKind::Turn, "test", 4).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData, "{err}");
+    }
+
+    #[test]
+    fn truncated_table_is_rejected() {
+        assert_rejected("truncated", |bytes| {
+            bytes.pop();
+        });
+        assert_rejected("truncated-header", |bytes| bytes.truncate(HEADER_SIZE - 1));
+    }
+}
@@ -0,0 +1,235 @@
+use std::{io, path::{Path, PathBuf}, sync::{Arc, Mutex, OnceLock}};
+
+use crate::{error::Error, progress::ProgressSink, moving::{sym::SymTable, turn::TurnTable}, state::{statecenters::StateCenters, statecorners::StateCorners, statefto2::StateFTO2, stateftocenters::StateFTOCenters, stateftocorners::StateFTOCorners, stateftoedgetrio::StateFTOEdgeTrio, statepyra::StatePyra, symhash::SymHash}};
+
+
+// Sym, Turn and SymHash Tables of the States, passed to everything that needs them
+// Each Group of Tables is loaded or generated, once it is required or first used,
+// so that every Puzzle only pays for its own Tables
+// The Progress of generating missing Tables, also Pruning Tables, goes to the Sink
+pub struct Tables {
+    dir: PathBuf,
+    progress: Arc<dyn ProgressSink>,
+    mode: Mode,
+    loading: Mutex<()>,
+    pyra: OnceLock<Arc<SymTables>>,
+    fto2: OnceLock<Arc<SymTables>>,
+    corners: OnceLock<Arc<SymTables>>,
+    centers: OnceLock<Arc<RawTables>>,
+    fto_corners: OnceLock<Arc<RawTables>>,
+    fto_centers: [OnceLock<Arc<RawTables>>; 2],
+    fto_edges: [OnceLock<Arc<RawTables>>; 4]
+}
+
+// Tables of the sym reduced States, of the Centers of the FTO2
+// and of the FTO Coordinates: the Corners, both Center Orbits and the four Edge Trios
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableGroup {
+    Pyra,
+    FTO2,
+    Corners,
+    Centers,
+    FTOCorners,
+    FTOCenters,
+    FTOEdges
+}
+
+impl TableGroup {
+    pub const ALL: [Self; 7] = [Self::Pyra, Self::FTO2, Self::Corners, Self::Centers, Self::FTOCorners, Self::FTOCenters, Self::FTOEdges];
+    pub const FTO: [Self; 3] = [Self::FTOCorners, Self::FTOCenters, Self::FTOEdges];
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Load,
+    Generate,
+    LoadOrGenerate
+}
+
+// SymHash Table, its Index and the Turn Table of a sym reduced State
+pub struct SymTables {
+    symhash: Vec<u32>,
+    symindex: Vec<u32>,
+    turn: Vec<u32>
+}
+
+impl SymTables {
+    pub fn symhash(&self) -> &Vec<u32> {
+        &self.symhash
+    }
+
+    pub fn symindex(&self) -> &Vec<u32> {
+        &self.symindex
+    }
+
+    pub fn turn(&self) -> &Vec<u32> {
+        &self.turn
+    }
+}
+
+// Turn and Sym Table of a State, which isn't sym reduced
+pub struct RawTables {
+    turn: Vec<u32>,
+    sym: Vec<u32>
+}
+
+impl RawTables {
+    pub fn turn(&self) -> &Vec<u32> {
+        &self.turn
+    }
+
+    pub fn sym(&self) -> &Vec<u32> {
+        &self.sym
+    }
+}
+
+impl Tables {
+    // Loads the required Tables from the Directory, fails if one is missing or invalid
+    pub fn load(dir: impl Into<PathBuf>, progress: Arc<dyn ProgressSink>) -> Self {
+        Self::new(dir.into(), progress, Mode::Load)
+    }
+
+    // Generates the required Tables and saves them into the Directory
+    pub fn generate(dir: impl Into<PathBuf>, progress: Arc<dyn ProgressSink>) -> Self {
+        Self::new(dir.into(), progress, Mode::Generate)
+    }
+
+    // Loads the required Tables from the Directory, generates the missing or invalid ones
+    pub fn load_or_generate(dir: impl Into<PathBuf>, progress: Arc<dyn ProgressSink>) -> Self {
+        Self::new(dir.into(), progress, Mode::LoadOrGenerate)
+    }
+
+    fn new(dir: PathBuf, progress: Arc<dyn ProgressSink>, mode: Mode) -> Self {
+        Self {
+            dir,
+            progress,
+            mode,
+            loading: Mutex::new(()),
+            pyra: OnceLock::new(),
+            fto2: OnceLock::new(),
+            corners: OnceLock::new(),
+            centers: OnceLock::new(),
+            fto_corners: OnceLock::new(),
+            fto_centers: [OnceLock::new(), OnceLock::new()],
+            fto_edges: [OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new()]
+        }
+    }
+
+    // Loads the Groups, which aren't loaded yet
+    pub fn require(&self, groups: &[TableGroup]) -> Result<(), Error> {
+        for group in groups {
+            match group {
+                TableGroup::Pyra => {self.pyra()?;}
+                TableGroup::FTO2 => {self.fto2()?;}
+                TableGroup::Corners => {self.corners()?;}
+                TableGroup::Centers => {self.centers()?;}
+                TableGroup::FTOCorners => {self.fto_corners()?;}
+                TableGroup::FTOCenters => {
+                    self.fto_centers::<1>()?;
+                    self.fto_centers::<2>()?;
+                }
+                TableGroup::FTOEdges => {
+                    self.fto_edges::<0>()?;
+                    self.fto_edges::<1>()?;
+                    self.fto_edges::<2>()?;
+                    self.fto_edges::<3>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn pyra(&self) -> Result<Arc<SymTables>, Error> {
+        self.group(&self.pyra, Self::build_sym_tables::<StatePyra>)
+    }
+
+    pub fn fto2(&self) -> Result<Arc<SymTables>, Error> {
+        self.group(&self.fto2, Self::build_sym_tables::<StateFTO2>)
+    }
+
+    pub fn corners(&self) -> Result<Arc<SymTables>, Error> {
+        self.group(&self.corners, Self::build_sym_tables::<StateCorners>)
+    }
+
+    pub fn centers(&self) -> Result<Arc<RawTables>, Error> {
+        self.group(&self.centers, Self::build_raw_tables::<StateCenters>)
+    }
+
+    pub fn fto_corners(&self) -> Result<Arc<RawTables>, Error> {
+        self.group(&self.fto_corners, Self::build_raw_tables::<StateFTOCorners>)
+    }
+
+    // Centers of the Orbit 1 or 2
+    pub fn fto_centers<const ORBIT: u8>(&self) -> Result<Arc<RawTables>, Error> {
+        self.group(&self.fto_centers[ORBIT as usize - 1], Self::build_raw_tables::<StateFTOCenters<ORBIT>>)
+    }
+
+    // Edges of the Trio 0 to 3
+    pub fn fto_edges<const TRIO: u8>(&self) -> Result<Arc<RawTables>, Error> {
+        self.group(&self.fto_edges[TRIO as usize], Self::build_raw_tables::<StateFTOEdgeTrio<TRIO>>)
+    }
+
+    // Loads or generates the Group on first use, later uses share it
+    fn group<G>(&self, group: &OnceLock<Arc<G>>, build: fn(&Self) -> Result<G, Error>) -> Result<Arc<G>, Error> {
+        if let Some(tables) = group.get() {
+            return Ok(tables.clone());
+        }
+        let _loading = self.loading.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(tables) = group.get() {
+            return Ok(tables.clone());
+        }
+        let tables = Arc::new(build(self)?);
+        let _ = group.set(tables.clone());
+        Ok(tables)
+    }
+
+    // Turn Tables of sym reduced States are generated after their SymHash Tables and Indices
+    // Once a Table of a Group is generated, the later Tables are generated too, since they are built from it
+    fn build_sym_tables<S: SymHash + TurnTable<Index = SymTables>>(&self) -> Result<SymTables, Error> {
+        let dir = &self.dir;
+        let progress = &*self.progress;
+        let (symhash, generated) = self.get_table(false, || <S as SymHash>::load_table(dir), || <S as SymHash>::generate_table(dir, progress))?;
+        let (symindex, generated) = self.get_table(generated, || S::load_index(dir), || S::generate_index(&symhash, dir, progress))?;
+        // The Turn Table is generated from the SymHash Table and its Index
+        let mut tables = SymTables { symhash, symindex, turn: vec![] };
+        let (turn, _) = self.get_table(generated, || <S as TurnTable>::load_table(dir), || <S as TurnTable>::generate_table(&tables, dir, progress))?;
+        tables.turn = turn;
+        Ok(tables)
+    }
+
+    fn build_raw_tables<S: TurnTable<Index = ()> + SymTable>(&self) -> Result<RawTables, Error> {
+        let dir = &self.dir;
+        let progress = &*self.progress;
+        Ok(RawTables {
+            turn: self.get_table(false, || <S as TurnTable>::load_table(dir), || <S as TurnTable>::generate_table(&(), dir, progress))?.0,
+            sym: self.get_table(false, || <S as SymTable>::load_table(dir), || <S as SymTable>::generate_table(dir, progress))?.0
+        })
+    }
+
+    // Returns whether the Table was generated, stale Tables are generated without loading them
+    fn get_table(&self, stale: bool, load: impl FnOnce() -> Result<Vec<u32>, io::Error>, generate: impl FnOnce() -> Result<Vec<u32>, Error>) -> Result<(Vec<u32>, bool), Error> {
+        let mode = if stale {Mode::Generate} else {self.mode};
+        match mode {
+            Mode::Load => Ok((load()?, false)),
+            Mode::Generate => Ok((generate()?, true)),
+            Mode::LoadOrGenerate => match load() {
+                Ok(table) => Ok((table, false)),
+                Err(err) => {
+                    // Stale or corrupted Tables get replaced
+                    if err.kind() == io::ErrorKind::InvalidData {
+                        self.progress.message(&err.to_string());
+                    }
+                    Ok((generate()?, true))
+                }
+            }
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn progress(&self) -> &dyn ProgressSink {
+        &*self.progress
+    }
+}
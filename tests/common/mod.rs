@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use std::{path::{Path, PathBuf}, process, sync::Mutex};
+
+use fto_solver::progress::ProgressSink;
+
+
+// Directory for the Tables of one Test, removed afterwards
+pub struct TempDir {
+    path: PathBuf
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("fto-solver-{name}-{}", process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self {
+            path
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+// Records the generated Tables and the Depths of the Pruning Tables
+#[derive(Default)]
+pub struct RecordingProgress {
+    pub started: Mutex<Vec<String>>,
+    pub messages: Mutex<Vec<String>>,
+    pub depth: Mutex<u8>,
+    // Deepest Depth, which filled Entries
+    pub max_depth: Mutex<u8>
+}
+
+impl ProgressSink for RecordingProgress {
+    fn start(&self, table: &str, _size: u64) {
+        self.started.lock().unwrap().push(table.to_string());
+    }
+
+    fn depth_start(&self, depth: u8, _states: u64) {
+        *self.depth.lock().unwrap() = depth;
+    }
+
+    fn message(&self, message: &str) {
+        self.messages.lock().unwrap().push(message.to_string());
+    }
+
+    fn entries_filled(&self, count: u64) {
+        if count > 0 {
+            let depth = *self.depth.lock().unwrap();
+            let mut max_depth = self.max_depth.lock().unwrap();
+            *max_depth = depth.max(*max_depth);
+        }
+    }
+}
+
+impl RecordingProgress {
+    // Names of the generated Tables as subdir/file
+    pub fn generated(&self) -> Vec<String> {
+        self.started.lock().unwrap().iter().map(|table| {
+            let path = Path::new(table);
+            let subdir = path.parent().and_then(Path::file_name).unwrap().to_string_lossy();
+            format!("{subdir}/{}", path.file_name().unwrap().to_string_lossy())
+        }).collect()
+    }
+}
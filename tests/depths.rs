@@ -0,0 +1,71 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{RecordingProgress, TempDir};
+use fto_solver::{pruning::{stateset::StateSet, statesetfto2::StateSetFTO2, statesetfto2split::StateSetFTO2Split, statesetftocenters::StateSetFTOCenters, statesetftocorners::StateSetFTOCorners, statesetftocornerscenters::StateSetFTOCornersCenters, statesetftoedges::StateSetFTOEdges, statesetftoedgescorners::StateSetFTOEdgesCorners, statesetftosubgroup::StateSetFTOSubgroup, statesetpyra::StateSetPyra, PruningTable}, tables::Tables};
+
+
+// Generates the Pruning Table and checks, that its deepest States are at MAX_DEPTH,
+// which the Heuristics use as the Bound of unfilled Entries
+fn assert_max_depth<S: StateSet + Sync + Send>() {
+    let dir = TempDir::new(S::NAME);
+    let progress = Arc::new(RecordingProgress::default());
+    PruningTable::<S>::new(&Tables::generate(dir.path(), progress.clone())).generate(false).unwrap();
+    assert_eq!(*progress.max_depth.lock().unwrap(), S::MAX_DEPTH, "{}", S::NAME);
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn pyra_max_depth() {
+    assert_max_depth::<StateSetPyra>();
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn fto2_max_depth() {
+    assert_max_depth::<StateSetFTO2>();
+    assert_max_depth::<StateSetFTO2Split>();
+}
+
+#[test]
+#[ignore = "generates the table, run with --release"]
+fn fto_edges_max_depth() {
+    assert_max_depth::<StateSetFTOEdges>();
+}
+
+#[test]
+#[ignore = "generates the table, run with --release"]
+fn fto_centers_max_depth() {
+    assert_max_depth::<StateSetFTOCenters>();
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn fto_corners_max_depth() {
+    assert_max_depth::<StateSetFTOCorners<0>>();
+    assert_max_depth::<StateSetFTOCorners<1>>();
+    assert_max_depth::<StateSetFTOCorners<2>>();
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn fto_edges_corners_max_depth() {
+    assert_max_depth::<StateSetFTOEdgesCorners<0>>();
+    assert_max_depth::<StateSetFTOEdgesCorners<1>>();
+    assert_max_depth::<StateSetFTOEdgesCorners<2>>();
+    assert_max_depth::<StateSetFTOEdgesCorners<3>>();
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn fto_corners_centers_max_depth() {
+    assert_max_depth::<StateSetFTOCornersCenters<1>>();
+    assert_max_depth::<StateSetFTOCornersCenters<2>>();
+}
+
+#[test]
+#[ignore = "generates the table, run with --release"]
+fn fto_subgroup_max_depth() {
+    assert_max_depth::<StateSetFTOSubgroup>();
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::TempDir;
+use fto_solver::{error::Error, progress::NoProgress, pruning::{heuristic::PruningHeuristic, stateset::StateSet, statesetftocenters::StateSetFTOCenters, statesetftocorners::StateSetFTOCorners, statesetftoedges::StateSetFTOEdges, PruningTable}, puzzle::Puzzle, solver::{solverfto::SolverFTO, solverftosubgroup::SolverFTOSubgroup, solverpyra::SolverPyra}, state::{statefto::StateFTO, statepyra::StatePyra, RawHash, State}, tables::Tables};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+
+// Turns of the short Scrambles
+const SCRAMBLE_LENGTH: usize = 4;
+
+// Pyra States without Syms, so that no Tables are needed to generate them
+struct StateSetRawPyra(StatePyra);
+
+impl StateSet for StateSetRawPyra {
+    const NAME: &str = "testrawpyra";
+    const SIZE: usize = StatePyra::RAW_SIZE;
+    const MAX_DEPTH: u8 = 11;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self(StatePyra::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self(StatePyra::from_hash(hash))
+    }
+
+    fn get_hash(&self) -> usize {
+        self.0.get_hash()
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        vec![]
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> {
+        self.0.get_next_states().into_iter().map(Self).collect()
+    }
+}
+
+// Heuristic of the States up to the Depth, the others are at least one deeper
+fn small_heuristic<S: State, T: StateSet + Sync + Send>(tables: &Arc<Tables>, depth: u8, num_turns: u8, hash: fn(&T::Lookup, &S) -> usize) -> PruningHeuristic<S, T> {
+    let table = PruningTable::<T>::new(tables).generate_to_depth(depth).unwrap();
+    PruningHeuristic::new(T::lookup(tables).unwrap(), table, depth + 1, num_turns, hash)
+}
+
+// The Stages of the Solver with small Tables solve short Scrambles,
+// the Tables of the Corners start from every Corner State, so they are only one Turn deep
+#[test]
+fn solver_solves_short_scrambles() {
+    let dir = TempDir::new("solve-short");
+    let tables = Arc::new(Tables::generate(dir.path(), Arc::new(NoProgress)));
+    let solver = SolverFTO::with_heuristics(vec![
+        Box::new(small_heuristic::<StateFTO, StateSetFTOEdges>(&tables, 5, StateFTO::NUM_TURNS, |_, fto| StateSetFTOEdges::from_fto(fto).get_hash())),
+        Box::new(small_heuristic::<StateFTO, StateSetFTOCenters>(&tables, 5, StateFTO::NUM_TURNS, |_, fto| StateSetFTOCenters::from_fto(fto).get_hash())),
+        Box::new(small_heuristic::<StateFTO, StateSetFTOCorners<0>>(&tables, 1, StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<0>::from_fto(fto).get_hash())),
+        Box::new(small_heuristic::<StateFTO, StateSetFTOCorners<1>>(&tables, 1, StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<1>::from_fto(fto).get_hash())),
+        Box::new(small_heuristic::<StateFTO, StateSetFTOCorners<2>>(&tables, 1, StateFTO::NUM_TURNS, |_, fto| StateSetFTOCorners::<2>::from_fto(fto).get_hash()))
+    ], SolverPyra::with_heuristic(small_heuristic::<StatePyra, StateSetRawPyra>(&tables, 6, StatePyra::NUM_TURNS, |_, pyra| pyra.get_hash())));
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..5 {
+        let mut fto = StateFTO::solved();
+        for _ in 0..SCRAMBLE_LENGTH {
+            fto.apply_turn(rng.random_range(0..StateFTO::NUM_TURNS));
+        }
+        let solution = solver.solve(&fto).unwrap();
+        fto.do_sequence(&solution);
+        assert_eq!(fto.to_facelets(), StateFTO::solved().to_facelets(), "{solution}");
+    }
+}
+
+
+// The Stages reduce the FTO into the Pyra Subgroup, whose Solution solves the whole FTO
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn solver_solves_scrambled_ftos() {
+    let dir = TempDir::new("solve-fto");
+    let solver = SolverFTO::new(Arc::new(Tables::load_or_generate(dir.path(), Arc::new(NoProgress)))).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..3 {
+        let mut fto = StateFTO::scrambled_with(&mut rng);
+        let solution = solver.solve(&fto).unwrap();
+        fto.do_sequence(&solution);
+        assert_eq!(fto.to_facelets(), StateFTO::solved().to_facelets(), "{solution}");
+    }
+}
+
+// Phase 1 ends in the Domain of StatePyra::from_fto, where the Distance is 0
+#[test]
+#[ignore = "generates the table, run with --release"]
+fn subgroup_solver_reaches_domain_of_from_fto() {
+    let dir = TempDir::new("solve-subgroup");
+    let solver = SolverFTOSubgroup::new(Arc::new(Tables::load_or_generate(dir.path(), Arc::new(NoProgress)))).unwrap();
+    let mut rng = StdRng::seed_from_u64(24);
+    for _ in 0..100 {
+        let mut fto = StateFTO::scrambled_with(&mut rng);
+        let distance = solver.distance(&fto);
+        assert_eq!(distance == 0, StatePyra::from_fto(&fto).is_ok());
+        let solution = solver.solve(&fto).unwrap();
+        assert_eq!(solution.len(), distance as usize, "{solution}");
+        fto.do_sequence(&solution);
+        assert!(StatePyra::from_fto(&fto).is_ok(), "{solution}");
+        assert_eq!(solver.distance(&fto), 0);
+    }
+    // Few Turns often keep the Edges in the Domain
+    for _ in 0..1000 {
+        let mut fto = StateFTO::solved();
+        for _ in 0..rng.random_range(0..4) {
+            fto.apply_turn(rng.random_range(0..StateFTO::NUM_TURNS));
+        }
+        assert_eq!(solver.distance(&fto) == 0, StatePyra::from_fto(&fto).is_ok());
+    }
+}
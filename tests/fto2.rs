@@ -0,0 +1,69 @@
+mod common;
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use common::TempDir;
+use fto_solver::{progress::NoProgress, solver::{solverfto2::SolverFTO2, solverfto2split::SolverFTO2Split}, state::{statefto2::StateFTO2, RawHash, State}, tables::Tables};
+use rand::{rngs::StdRng, SeedableRng};
+
+
+fn scrambled_fto2s(seed: u64, count: usize) -> Vec<StateFTO2> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count).map(|_| StateFTO2::scrambled_with(&mut rng)).collect()
+}
+
+fn solvers(dir: &TempDir) -> (SolverFTO2, SolverFTO2Split) {
+    let tables = Arc::new(Tables::load_or_generate(dir.path(), Arc::new(NoProgress)));
+    (SolverFTO2::new(tables.clone()).unwrap(), SolverFTO2Split::new(tables).unwrap())
+}
+
+// The optimal Solutions come first and are all as long as the Distance,
+// every Solution solves the State and none is longer than the next
+fn assert_solutions(fto2: &StateFTO2, distance: u8, optimal: &[Vec<u8>], solutions: &[Vec<u8>]) {
+    assert!(!optimal.is_empty());
+    assert!(optimal.iter().all(|solution| solution.len() == distance as usize));
+    assert_eq!(&solutions[..optimal.len()], optimal);
+    assert!(solutions[optimal.len()].len() > distance as usize);
+    assert!(solutions.windows(2).all(|pair| pair[0].len() <= pair[1].len()));
+    for solution in solutions {
+        let mut solved = fto2.clone();
+        solution.iter().for_each(|&turn| solved.apply_turn(turn));
+        assert_eq!(solved.get_hash(), StateFTO2::solved().get_hash(), "{solution:?}");
+    }
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn distance_is_length_of_solution() {
+    let dir = TempDir::new("fto2-distance");
+    let (solver, solver_split) = solvers(&dir);
+    assert_eq!(solver.distance(&StateFTO2::solved()), 0);
+    assert_eq!(solver_split.distance(&StateFTO2::solved()), 0);
+    let fto2s = scrambled_fto2s(3, 20);
+    for fto2 in &fto2s {
+        let solution = solver.solve(fto2).unwrap();
+        assert_eq!(solver.distance(fto2) as usize, solution.len(), "{solution}");
+        let solution = solver_split.solve(fto2).unwrap();
+        assert_eq!(solver_split.distance(fto2) as usize, solution.len(), "{solution}");
+    }
+    let distances: Vec<u8> = fto2s.iter().map(|fto2| solver.distance(fto2)).collect();
+    assert_eq!(solver.distances(&fto2s), distances);
+    assert_eq!(solver_split.distances(&fto2s), distances);
+}
+
+// Both Solvers find the same optimal Solutions
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn solutions_solve_the_state() {
+    let dir = TempDir::new("fto2-solutions");
+    let (solver, solver_split) = solvers(&dir);
+    for fto2 in scrambled_fto2s(4, 10) {
+        let optimal: Vec<Vec<u8>> = solver.optimal_solutions(&fto2).map(|solution| solution.turns()).collect();
+        let solutions: Vec<Vec<u8>> = solver.solutions(&fto2).take(optimal.len() + 10).map(|solution| solution.turns()).collect();
+        assert_solutions(&fto2, solver.distance(&fto2), &optimal, &solutions);
+        let optimal_split: Vec<Vec<u8>> = solver_split.optimal_solutions(&fto2).map(|solution| solution.turns()).collect();
+        let solutions_split: Vec<Vec<u8>> = solver_split.solutions(&fto2).take(optimal_split.len() + 10).map(|solution| solution.turns()).collect();
+        assert_solutions(&fto2, solver_split.distance(&fto2), &optimal_split, &solutions_split);
+        assert_eq!(BTreeSet::from_iter(optimal), BTreeSet::from_iter(optimal_split));
+    }
+}
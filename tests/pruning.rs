@@ -0,0 +1,175 @@
+mod common;
+
+use std::{collections::BTreeMap, fs, sync::{Arc, Mutex}};
+
+use common::{RecordingProgress, TempDir};
+use fto_solver::{error::Error, progress::ProgressSink, pruning::{stateset::StateSet, statesetftocornerscenters::StateSetFTOCornersCenters, statesetftoedgescorners::StateSetFTOEdgesCorners, PruningTable}, state::{statecorners::StateCorners, statefto::StateFTO, RawHash, State}, tables::{SymTables, Tables}};
+use rand::{rngs::StdRng, SeedableRng};
+
+
+// Small State Set without Tables, so that Tables are generated quickly
+struct StateSetCorners(StateCorners);
+
+impl StateSet for StateSetCorners {
+    const NAME: &str = "testcorners";
+    const SIZE: usize = StateCorners::RAW_SIZE;
+    const MAX_DEPTH: u8 = 8;
+
+    type Lookup = ();
+
+    fn lookup(_tables: &Tables) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn solved(_lookup: &()) -> Self {
+        Self(StateCorners::solved())
+    }
+
+    fn from_hash(hash: usize) -> Self {
+        Self(StateCorners::from_hash(hash))
+    }
+
+    fn get_hash(&self) -> usize {
+        self.0.get_hash()
+    }
+
+    fn get_sym_hashes(&self, _lookup: &()) -> Vec<usize> {
+        self.0.get_sym_states().iter().map(StateCorners::get_hash).collect()
+    }
+
+    fn get_next_state_sets(&self, _lookup: &()) -> Vec<Self> {
+        self.0.get_next_states().into_iter().map(Self).collect()
+    }
+}
+
+// Depths mod 3 of a forward Search over all States, 3 for unreached States
+fn forward_depthsm3() -> Vec<u8> {
+    let mut depths = vec![3; StateSetCorners::SIZE];
+    let solved = StateSetCorners::solved(&());
+    let mut closed = vec![solved.get_hash()];
+    depths[solved.get_hash()] = 0;
+    let mut depth = 0;
+    while !closed.is_empty() {
+        depth += 1;
+        closed = closed.into_iter().flat_map(|hash| StateSetCorners::from_hash(hash).get_next_state_sets(&())).filter_map(|next| {
+            let hash = next.get_hash();
+            if depths[hash] == 3 {
+                depths[hash] = depth % 3;
+                Some(hash)
+            } else {
+                None
+            }
+        }).collect();
+    }
+    depths
+}
+
+// Keeps a Copy of the Checkpoint, which is saved before each Depth starts
+struct CheckpointCopies {
+    checkpoint: String,
+    copies: Mutex<BTreeMap<u8, Vec<u8>>>
+}
+
+impl ProgressSink for CheckpointCopies {
+    fn depth_start(&self, depth: u8, _states: u64) {
+        if let Ok(data) = fs::read(&self.checkpoint) {
+            self.copies.lock().unwrap().insert(depth, data);
+        }
+    }
+}
+
+// Resuming at any Depth, forward or backward, gives the Table of an uninterrupted Run
+#[test]
+fn resumed_generation_matches_uninterrupted() {
+    let dir = TempDir::new("resumed-generation");
+    let checkpoint = PruningTable::<StateSetCorners>::get_checkpoint_name(dir.path());
+    let copies = Arc::new(CheckpointCopies {
+        checkpoint: checkpoint.clone(),
+        copies: Mutex::new(BTreeMap::new())
+    });
+    let uninterrupted = PruningTable::<StateSetCorners>::new(&Tables::generate(dir.path(), copies.clone())).generate(false).unwrap();
+
+    let copies = copies.copies.lock().unwrap();
+    assert_eq!(copies.keys().copied().collect::<Vec<u8>>(), (2..=StateSetCorners::MAX_DEPTH + 1).collect::<Vec<u8>>());
+    for (depth, data) in copies.iter() {
+        fs::write(&checkpoint, data).unwrap();
+        let progress = Arc::new(RecordingProgress::default());
+        let resumed = PruningTable::<StateSetCorners>::new(&Tables::generate(dir.path(), progress.clone())).generate(true).unwrap();
+        assert!(progress.messages.lock().unwrap().contains(&format!("Resuming at Pruning Depth {depth}")));
+        assert!(resumed == uninterrupted, "Table resumed at depth {depth} differs");
+        assert!(fs::metadata(&checkpoint).is_err());
+    }
+}
+
+// The last Depths are searched backward, which fills the same Entries as searching forward
+#[test]
+fn backward_search_matches_forward_search() {
+    let dir = TempDir::new("backward-search");
+    let progress = Arc::new(RecordingProgress::default());
+    let tables = Tables::generate(dir.path(), progress.clone());
+    let table = PruningTable::<StateSetCorners>::new(&tables).generate(false).unwrap();
+    assert!(progress.messages.lock().unwrap().iter().any(|message| message == "Searching backward"));
+
+    let forward = forward_depthsm3();
+    assert!(forward.iter().all(|&depthm3| depthm3 != 3));
+    for (hash, &depthm3) in forward.iter().enumerate() {
+        assert_eq!(PruningTable::<StateSetCorners>::read(&table, hash), depthm3, "Hash {hash}");
+    }
+}
+
+// The Corner Sym s is the FTO Sym s << 1, so Syms of the Corners don't change the Hash
+fn assert_corner_syms_keep_hash<const ORBIT: u8>(corners: &SymTables) {
+    let mut rng = StdRng::seed_from_u64(23);
+    for _ in 0..20 {
+        let fto = StateFTO::scrambled_with(&mut rng);
+        let hash = StateSetFTOCornersCenters::<ORBIT>::from_fto(corners, &fto).get_hash();
+        for sym in 0..StateCorners::NUM_SYMS {
+            let mut wrapped = fto.clone();
+            wrapped.wrap_sym(sym << 1);
+            assert_eq!(StateSetFTOCornersCenters::<ORBIT>::from_fto(corners, &wrapped).get_hash(), hash, "sym {sym}");
+        }
+    }
+}
+
+#[test]
+fn corner_syms_keep_corners_centers_hash() {
+    let dir = TempDir::new("corner-syms");
+    let tables = Tables::generate(dir.path(), Arc::new(RecordingProgress::default()));
+    let corners = tables.corners().unwrap();
+    assert_corner_syms_keep_hash::<1>(&corners);
+    assert_corner_syms_keep_hash::<2>(&corners);
+}
+
+// The Turn Tables of the FTO Coordinates turn the State Sets like the FTO,
+// sym reduced State Sets turn a Sym of the FTO, so their Turns come in another Order,
+// and symmetric Corners leave the Sym of the Centers open, so the smallest Sym Hash is compared
+fn assert_turns_like_fto<T: StateSet>(tables: &Tables, from_fto: impl Fn(&T::Lookup, &StateFTO) -> T) {
+    let lookup = T::lookup(tables).unwrap();
+    let min_hash = |state: &T| state.get_sym_hashes(&lookup).into_iter().fold(state.get_hash(), usize::min);
+    let mut rng = StdRng::seed_from_u64(22);
+    for _ in 0..20 {
+        let fto = StateFTO::scrambled_with(&mut rng);
+        let mut next: Vec<usize> = from_fto(&lookup, &fto).get_next_state_sets(&lookup).iter().map(min_hash).collect();
+        let mut expected: Vec<usize> = fto.get_next_states().iter().map(|fto| min_hash(&from_fto(&lookup, fto))).collect();
+        next.sort();
+        expected.sort();
+        assert_eq!(next, expected, "{}", T::NAME);
+    }
+}
+
+#[test]
+fn fto_edge_tables_turn_like_fto() {
+    let dir = TempDir::new("fto-edge-tables");
+    let tables = Tables::generate(dir.path(), Arc::new(RecordingProgress::default()));
+    assert_turns_like_fto::<StateSetFTOEdgesCorners<0>>(&tables, |_, fto| StateSetFTOEdgesCorners::<0>::from_fto(fto));
+    assert_turns_like_fto::<StateSetFTOEdgesCorners<3>>(&tables, |_, fto| StateSetFTOEdgesCorners::<3>::from_fto(fto));
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn fto_center_tables_turn_like_fto() {
+    let dir = TempDir::new("fto-center-tables");
+    let tables = Tables::generate(dir.path(), Arc::new(RecordingProgress::default()));
+    assert_turns_like_fto::<StateSetFTOCornersCenters<1>>(&tables, |(corners, _, _), fto| StateSetFTOCornersCenters::<1>::from_fto(corners, fto));
+    assert_turns_like_fto::<StateSetFTOCornersCenters<2>>(&tables, |(corners, _, _), fto| StateSetFTOCornersCenters::<2>::from_fto(corners, fto));
+}
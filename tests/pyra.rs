@@ -0,0 +1,55 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::TempDir;
+use fto_solver::{progress::NoProgress, solver::solverpyra::SolverPyra, state::{statepyra::StatePyra, RawHash, State}, tables::Tables};
+use rand::{rngs::StdRng, SeedableRng};
+
+
+fn scrambled_pyras(seed: u64, count: usize) -> Vec<StatePyra> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..count).map(|_| StatePyra::scrambled_with(&mut rng)).collect()
+}
+
+// The optimal Solutions come first and are all as long as the Distance,
+// every Solution solves the State and none is longer than the next
+fn assert_solutions(pyra: &StatePyra, distance: u8, optimal: &[Vec<u8>], solutions: &[Vec<u8>]) {
+    assert!(!optimal.is_empty());
+    assert!(optimal.iter().all(|solution| solution.len() == distance as usize));
+    assert_eq!(&solutions[..optimal.len()], optimal);
+    assert!(solutions[optimal.len()].len() > distance as usize);
+    assert!(solutions.windows(2).all(|pair| pair[0].len() <= pair[1].len()));
+    for solution in solutions {
+        let mut solved = pyra.clone();
+        solution.iter().for_each(|&turn| solved.apply_turn(turn));
+        assert_eq!(solved.get_hash(), StatePyra::solved().get_hash(), "{solution:?}");
+    }
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn distance_is_length_of_solution() {
+    let dir = TempDir::new("pyra-distance");
+    let solver = SolverPyra::new(Arc::new(Tables::load_or_generate(dir.path(), Arc::new(NoProgress)))).unwrap();
+    assert_eq!(solver.distance(&StatePyra::solved()), 0);
+    let pyras = scrambled_pyras(3, 20);
+    for pyra in &pyras {
+        let solution = solver.solve(pyra).unwrap();
+        assert_eq!(solver.distance(pyra) as usize, solution.len(), "{solution}");
+    }
+    let distances: Vec<u8> = pyras.iter().map(|pyra| solver.distance(pyra)).collect();
+    assert_eq!(solver.distances(&pyras), distances);
+}
+
+#[test]
+#[ignore = "generates the tables, run with --release"]
+fn solutions_solve_the_state() {
+    let dir = TempDir::new("pyra-solutions");
+    let solver = SolverPyra::new(Arc::new(Tables::load_or_generate(dir.path(), Arc::new(NoProgress)))).unwrap();
+    for pyra in scrambled_pyras(4, 10) {
+        let optimal: Vec<Vec<u8>> = solver.optimal_solutions(&pyra).map(|solution| solution.turns()).collect();
+        let solutions: Vec<Vec<u8>> = solver.solutions(&pyra).take(optimal.len() + 10).map(|solution| solution.turns()).collect();
+        assert_solutions(&pyra, solver.distance(&pyra), &optimal, &solutions);
+    }
+}
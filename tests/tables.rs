@@ -0,0 +1,34 @@
+mod common;
+
+use std::{fs, sync::Arc};
+
+use common::{RecordingProgress, TempDir};
+use fto_solver::{progress::NoProgress, tables::{TableGroup, Tables}};
+
+
+// Index and Turn Table are built from the SymHash Table, so they can't be kept after it changed
+#[test]
+fn regenerated_symhash_regenerates_its_group() {
+    let dir = TempDir::new("regenerated-symhash");
+    Tables::generate(dir.path(), Arc::new(NoProgress)).require(&[TableGroup::Corners]).unwrap();
+
+    let progress = Arc::new(RecordingProgress::default());
+    Tables::load_or_generate(dir.path(), progress.clone()).require(&[TableGroup::Corners]).unwrap();
+    assert!(progress.generated().is_empty());
+
+    fs::remove_file(dir.path().join("symhash").join("corners.bin")).unwrap();
+    let progress = Arc::new(RecordingProgress::default());
+    Tables::load_or_generate(dir.path(), progress.clone()).require(&[TableGroup::Corners]).unwrap();
+    assert_eq!(progress.generated(), ["symhash/corners.bin", "symindex/corners.bin", "turn/corners.bin"]);
+}
+
+// Missing Tables fail the Use of their Group, a later Use loads them, once they exist, and keeps them
+#[test]
+fn missing_tables_fail_on_first_use() {
+    let dir = TempDir::new("missing-tables");
+    let tables = Tables::load(dir.path(), Arc::new(NoProgress));
+    assert!(tables.corners().is_err());
+    Tables::generate(dir.path(), Arc::new(NoProgress)).require(&[TableGroup::Corners]).unwrap();
+    let corners = tables.corners().unwrap();
+    assert_eq!(Arc::as_ptr(&corners), Arc::as_ptr(&tables.corners().unwrap()));
+}